@@ -0,0 +1,40 @@
+//! Draws the shortest hiking route from the start square and from the best square at height `a`
+//! over the day 12 heightmap, in the puzzle's `^v<>` style.
+//!
+//! Usage: cargo run --release --example day_12_route -- [input]
+
+use std::{env, fs, process};
+use advent_of_code_2022::{error::Error, solution::Solution, solutions::day_12::{self, Day12}};
+
+const INPUT: &str = "./input/12";
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| INPUT.to_string());
+
+    let squares = fs::read_to_string(&path)
+        .map_err(|err| Error::Input(path.clone(), err))
+        .and_then(|input| Ok(Day12::parse(&input)?));
+
+    match squares {
+        Ok(squares) => {
+            let routes = [
+                ("the start", day_12::get_route(&squares)),
+                ("the best a", day_12::get_best_route(&squares)),
+            ];
+
+            for (from, route) in routes {
+                match route {
+                    Some(route) => {
+                        println!("{} steps from {}:", route.len() - 1, from);
+                        println!("{}", day_12::render(&squares, &route));
+                    },
+                    None => println!("no route from {}", from),
+                }
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        },
+    }
+}
@@ -0,0 +1,40 @@
+//! Prints the best build order for each day 19 blueprint, minute by minute, in the puzzle's
+//! narrative style.
+//!
+//! Usage: cargo run --release --example day_19_trace -- [input] [minutes]
+
+use std::{env, fs, process};
+use advent_of_code_2022::{error::Error, solution::Solution, solutions::day_19::{self, Day19}};
+
+const INPUT: &str = "./input/19";
+const MINUTES: usize = 24;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| INPUT.to_string());
+
+    let minutes = match args.next().map(|minutes| minutes.parse()) {
+        None => MINUTES,
+        Some(Ok(minutes)) => minutes,
+        Some(Err(err)) => {
+            eprintln!("error: couldn't parse minutes: {}", err);
+            process::exit(1);
+        },
+    };
+
+    let blueprints = fs::read_to_string(&path)
+        .map_err(|err| Error::Input(path.clone(), err))
+        .and_then(|input| Ok(Day19::parse(&input)?));
+
+    match blueprints {
+        Ok(blueprints) => {
+            for plan in day_19::get_plans(&blueprints, minutes) {
+                println!("{}", plan);
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        },
+    }
+}
@@ -0,0 +1,128 @@
+use std::{hint::black_box, time::{Duration, Instant}};
+use crate::{error::ParseError, solution::{Part, Solution}};
+
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    fn new(mut times: Vec<Duration>) -> Stats {
+        times.sort();
+
+        let total: Duration = times.iter().sum();
+        let p95_rank = (times.len() * 95).div_ceil(100).max(1);
+
+        Stats {
+            mean: total / times.len() as u32,
+            median: times[times.len() / 2],
+            p95: times[p95_rank - 1],
+        }
+    }
+}
+
+pub struct Bench {
+    pub day: u8,
+    pub parse: Stats,
+    pub parts: Vec<(u8, Stats)>,
+}
+
+/// Times `f` over `runs` runs after `warm_up` untimed ones.
+fn time<T>(runs: usize, warm_up: usize, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..warm_up {
+        black_box(f());
+    }
+
+    let times = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::new(times)
+}
+
+/// Benchmarks parsing and each requested part separately, timing every stage over `runs` runs.
+/// `runs` must be at least 1.
+pub fn bench<S: Solution>(input: &str, part: &Part, runs: usize, warm_up: usize) -> Result<Bench, ParseError> {
+    let parsed = S::parse(input)?;
+    let parse = time(runs, warm_up, || S::parse(input));
+
+    let parts = part.numbers()
+        .iter()
+        .map(|number| {
+            let stats = match number {
+                1 => time(runs, warm_up, || S::part_one(&parsed)),
+                _ => time(runs, warm_up, || S::part_two(&parsed)),
+            };
+
+            (*number, stats)
+        })
+        .collect();
+
+    Ok(Bench {
+        day: S::DAY,
+        parse,
+        parts,
+    })
+}
+
+fn stage_json(day: u8, stage: &str, stats: &Stats) -> String {
+    format!(
+        "    {{\"day\": {}, \"stage\": \"{}\", \"mean_ns\": {}, \"median_ns\": {}, \"p95_ns\": {}}}",
+        day,
+        stage,
+        stats.mean.as_nanos(),
+        stats.median.as_nanos(),
+        stats.p95.as_nanos(),
+    )
+}
+
+pub fn to_json(benches: &[Bench], runs: usize, warm_up: usize) -> String {
+    let stages = benches
+        .iter()
+        .flat_map(|bench| {
+            let parse = stage_json(bench.day, "parse", &bench.parse);
+            let parts = bench.parts
+                .iter()
+                .map(|(part, stats)| stage_json(bench.day, &format!("part {}", part), stats));
+
+            std::iter::once(parse).chain(parts).collect::<Vec<String>>()
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "{{\n  \"runs\": {},\n  \"warm_up\": {},\n  \"results\": [\n{}\n  ]\n}}\n",
+        runs,
+        warm_up,
+        stages.join(",\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(times: &[u64]) -> Vec<Duration> {
+        times.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(millis(&[5, 1, 4, 2, 3]));
+
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn p95() {
+        let stats = Stats::new(millis(&(1..=100).collect::<Vec<u64>>()));
+
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+}
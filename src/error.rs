@@ -0,0 +1,120 @@
+use std::{fmt, io, str::FromStr};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn on_day(self, day: u8) -> ParseError {
+        ParseError { day, ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {:?}",
+            self.day, self.line, self.column, self.expected, self.text,
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum Error {
+    Input(String, io::Error),
+    Output(String, io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Input(path, err) => write!(f, "couldn't read {}: {}", path, err),
+            Error::Output(path, err) => write!(f, "couldn't write {}: {}", path, err),
+            Error::Parse(err) => write!(f, "couldn't parse input: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        Error::Parse(err)
+    }
+}
+
+/// A line of puzzle input along with its 1-based line number, used to locate parse errors.
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Creates an error for `token`, which should be a slice of this line so that its column
+    /// can be worked out.
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+        let column = if offset <= self.text.len() {
+            offset + 1
+        } else {
+            self.text.find(token).map_or(0, |offset| offset + 1)
+        };
+
+        ParseError {
+            day: 0,
+            line: self.number,
+            column,
+            text: token.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Creates an error for the whole line.
+    pub fn invalid(&self, expected: &str) -> ParseError {
+        self.error(self.text, expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, "a number"))
+    }
+
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, &format!("{:?}", prefix)))
+    }
+
+    pub fn split_once(&self, text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        text.split_once(delimiter)
+            .ok_or_else(|| self.error(text, &format!("{:?}", delimiter)))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line { number: idx + 1, text })
+}
+
+/// Creates an error for something missing from the end of `input`.
+pub fn end_of_input(input: &str, expected: &str) -> ParseError {
+    ParseError {
+        day: 0,
+        line: input.lines().count() + 1,
+        column: 1,
+        text: String::new(),
+        expected: expected.to_string(),
+    }
+}
@@ -0,0 +1,250 @@
+use std::{fmt, ops::{Index, IndexMut}};
+use crate::{error::{self, ParseError}, point::{Compass, Dir, Point}};
+
+/// A position in a grid, where `y` counts rows down from the top.
+pub type Coord = Point<usize>;
+
+/// A dense rectangular grid, stored row by row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> where T: Clone {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a grid with a character per cell, erroring on any character `parse_cell` doesn't
+    /// accept or any row that's a different width to the first. `expected` describes the
+    /// characters that are accepted.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        parse_cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.len());
+        let mut cells = vec!();
+        let mut height = 0;
+
+        for line in error::lines(input) {
+            if line.text.len() != width {
+                return Err(line.invalid(&format!("a row {} wide", width)));
+            }
+
+            for (idx, c) in line.text.char_indices() {
+                let cell = parse_cell(c)
+                    .ok_or_else(|| line.error(&line.text[idx..idx + c.len_utf8()], expected))?;
+
+                cells.push(cell);
+            }
+
+            height += 1;
+        }
+
+        if cells.is_empty() {
+            return Err(error::end_of_input(input, "a grid"));
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, Point { x, y }: Coord) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, Point { x, y }: Coord) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Adds a row to the bottom of the grid, which must be as wide as the grid.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        assert_eq!(row.len(), self.width, "the row is the wrong width");
+
+        self.cells.extend(row);
+        self.height += 1;
+    }
+
+    /// The position `delta` away from `coord`, if it's still in the grid.
+    pub fn offset(&self, coord: Coord, delta: Point) -> Option<Coord> {
+        let x = coord.x.checked_add_signed(delta.x)?;
+        let y = coord.y.checked_add_signed(delta.y)?;
+
+        if x < self.width && y < self.height {
+            Some(Point::new(x, y))
+        } else {
+            None
+        }
+    }
+
+    /// The positions above, right, below and left of `coord` that are in the grid.
+    pub fn neighbours(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Dir::ALL
+            .iter()
+            .filter_map(move |dir| self.offset(coord, dir.delta()))
+    }
+
+    /// All eight positions around `coord` that are in the grid, including diagonals.
+    pub fn adjacent(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Compass::ALL
+            .iter()
+            .filter_map(move |dir| self.offset(coord, dir.delta()))
+    }
+
+    /// The positions from `coord` to the edge of the grid going in steps of `delta`, not
+    /// including `coord` itself.
+    pub fn ray(&self, coord: Coord, delta: Point) -> impl Iterator<Item = Coord> + '_ {
+        let mut current = Some(coord);
+
+        std::iter::from_fn(move || {
+            current = self.offset(current?, delta);
+            current
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width)
+    }
+
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+
+        (0..self.cells.len()).map(move |idx| Point::new(idx % width, idx / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// The first position, reading row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|idx| Point::new(idx % self.width, idx / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord).unwrap_or_else(|| panic!("{} is outside the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord).unwrap_or_else(|| panic!("{} is outside the grid", coord))
+    }
+}
+
+/// Draws the grid a row per line, with each cell's `Display` output next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+123
+456";
+
+    fn example() -> Grid<u32> {
+        Grid::parse(EXAMPLE, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = example();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Grid::parse("12\n1x", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        let err = Grid::parse("12\n123", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example();
+
+        assert_eq!(grid.neighbours(Point::new(0, 0)).collect::<Vec<Coord>>(), vec!(Point::new(1, 0), Point::new(0, 1)));
+        assert_eq!(grid.adjacent(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = example();
+
+        assert_eq!(
+            grid.ray(Point::new(0, 0), Dir::Right.delta()).collect::<Vec<Coord>>(),
+            vec!(Point::new(1, 0), Point::new(2, 0)),
+        );
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec!(2, 5));
+    }
+}
@@ -0,0 +1,11 @@
+//! Solutions to Advent of Code 2022, with each day's parser, parsed input types and solvers
+//! exposed for reuse.
+
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod parallel;
+pub mod point;
+pub mod search;
+pub mod solution;
+pub mod solutions;
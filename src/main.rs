@@ -1,23 +1,425 @@
-#![allow(dead_code)]
-use std::fs;
+use std::{fs, io::{self, Read}, process, str::FromStr, time::Duration};
 use structopt::StructOpt;
 
+use advent_of_code_2022::{
+    bench::{self, Bench},
+    error::Error,
+    parallel,
+    solution::{Answer, Part, Run},
+    solutions::{self, Day},
+};
+
 #[derive(StructOpt, Debug)]
 struct Opt {
     #[structopt(short, long)]
     test: bool,
+
+    /// Read the puzzle input from this file, or from stdin if it's -
+    #[structopt(short, long, conflicts_with_all = &["test", "all"])]
+    input: Option<String>,
+
+    /// Day to run, defaults to the latest implemented day with an input file
+    #[structopt(short, long)]
+    day: Option<u8>,
+
+    /// Part to run: 1, 2 or both
+    #[structopt(short, long, default_value = "both")]
+    part: Part,
+
+    /// Run both parts of every implemented day and print a summary table
+    #[structopt(short, long, conflicts_with_all = &["day", "part", "test"])]
+    all: bool,
+
+    /// Compare answers with those recorded in ./answers and fail on any mismatch
+    #[structopt(short, long, conflicts_with_all = &["test", "input"])]
+    verify: bool,
+
+    /// Benchmark parsing and solving over this many runs, writing the results to --bench-output
+    #[structopt(short, long, conflicts_with = "verify")]
+    bench: Option<usize>,
+
+    /// Untimed runs before each benchmark
+    #[structopt(long, default_value = "1")]
+    warm_up: usize,
+
+    /// File to write benchmark results to as JSON
+    #[structopt(long, default_value = "./bench.json")]
+    bench_output: String,
+
+    /// Threads for days that search in parallel, defaults to one per core; 1 forces single-threaded
+    #[structopt(short = "j", long)]
+    threads: Option<usize>,
+
+    /// Output format for answers: text, or json for one JSON object per line
+    #[structopt(short, long, default_value = "text")]
+    format: Format,
+}
+
+#[derive(Debug)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("expected text or json, got {}", format)),
+        }
+    }
 }
 
-mod solutions { pub mod day_19; }
-use solutions::day_19::solve;
-const INPUT: &str = "./input/19";
 const TEST_INPUT: &str = "./input/test";
+const STDIN: &str = "-";
+
+fn input_path(day: u8) -> String {
+    format!("./input/{:02}", day)
+}
+
+fn read_input(path: &str) -> Result<String, Error> {
+    if path == STDIN {
+        let mut input = String::new();
+
+        return io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| input)
+            .map_err(|err| Error::Input("stdin".to_string(), err));
+    }
+
+    fs::read_to_string(path).map_err(|err| Error::Input(path.to_string(), err))
+}
+
+fn answer_path(day: u8, part: u8) -> String {
+    format!("./answers/{:02}-{}", day, part)
+}
+
+enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+impl Verdict {
+    fn check(day: u8, part: u8, answer: &str) -> Verdict {
+        match fs::read_to_string(answer_path(day, part)) {
+            Ok(expected) if expected.trim_end() == answer.trim_end() => Verdict::Correct,
+            Ok(expected) => Verdict::Wrong(expected.trim_end().to_string()),
+            Err(_) => Verdict::Unknown,
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            Verdict::Correct => "ok",
+            Verdict::Wrong(_) => "WRONG",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+fn print_result(part: u8, answer: &str, verdict: Option<&Verdict>) {
+    if answer.contains('\n') {
+        print!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+
+    match verdict {
+        Some(Verdict::Wrong(expected)) if expected.contains('\n') => {
+            println!("  WRONG, expected:\n{}", expected)
+        },
+        Some(Verdict::Wrong(expected)) => println!("  WRONG, expected {}", expected),
+        Some(Verdict::Unknown) => println!("  no recorded answer"),
+        _ => (),
+    }
+}
+
+struct Row {
+    day: u8,
+    part: u8,
+    answer: Answer,
+    parse_time: Duration,
+    solve_time: Duration,
+    verdict: Option<Verdict>,
+}
+
+impl Row {
+    fn to_json(&self) -> String {
+        let check = match &self.verdict {
+            Some(verdict) => format!(", \"check\": \"{}\"", verdict.label().to_lowercase()),
+            None => String::new(),
+        };
+
+        format!(
+            "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"type\": \"{}\", \"parse_ns\": {}, \"solve_ns\": {}{}}}",
+            self.day,
+            self.part,
+            self.answer.to_json(),
+            self.answer.kind(),
+            self.parse_time.as_nanos(),
+            self.solve_time.as_nanos(),
+            check,
+        )
+    }
+}
+
+fn get_rows(day: u8, run: Run, verify: bool) -> Vec<Row> {
+    run.results
+        .into_iter()
+        .map(|result| {
+            let verdict = if verify {
+                Some(Verdict::check(day, result.part, &result.answer.to_string()))
+            } else {
+                None
+            };
+
+            Row {
+                day,
+                part: result.part,
+                answer: result.answer,
+                parse_time: run.parse_time,
+                solve_time: result.time,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+fn all_passed(rows: &[Row]) -> bool {
+    rows.iter().all(|row| !matches!(row.verdict, Some(Verdict::Wrong(_))))
+}
+
+/// Runs a single day, returning whether every checked answer matched its recorded answer.
+fn run_day(day: &Day, input: &str, part: &Part, verify: bool, format: &Format) -> Result<bool, Error> {
+    let rows = get_rows(day.number, (day.run)(input, part)?, verify);
+
+    for row in &rows {
+        match format {
+            Format::Text => print_result(row.part, &row.answer.to_string(), row.verdict.as_ref()),
+            Format::Json => println!("{}", row.to_json()),
+        }
+    }
+
+    Ok(all_passed(&rows))
+}
+
+/// Runs every day with an input, returning whether every day parsed and every checked answer
+/// matched its recorded answer.
+fn run_all(verify: bool, format: &Format) -> bool {
+    let mut rows = vec!();
+    let mut parsed = true;
+
+    for day in solutions::DAYS {
+        let input = match read_input(&input_path(day.number)) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("skipping day {}: {}", day.number, err);
+                continue;
+            },
+        };
+
+        match (day.run)(&input, &Part::Both) {
+            Ok(run) => rows.extend(get_rows(day.number, run, verify)),
+            Err(err) => {
+                eprintln!("error: {}", Error::from(err));
+                parsed = false;
+            },
+        }
+    }
+
+    match format {
+        Format::Text => print_table(&rows),
+        Format::Json => rows.iter().for_each(|row| println!("{}", row.to_json())),
+    }
+
+    parsed && all_passed(&rows)
+}
+
+fn print_table(rows: &[Row]) {
+    let answers: Vec<String> = rows.iter().map(|row| row.answer.to_string()).collect();
+
+    let answer_width = answers
+        .iter()
+        .flat_map(|answer| answer.lines())
+        .map(|line| line.len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "Day  Part  {:<width$}  {:>10}  {:>10}  Check",
+        "Answer",
+        "Parse",
+        "Solve",
+        width = answer_width,
+    );
+    println!("{}", "-".repeat(answer_width + 41));
+
+    let mut total = Duration::ZERO;
+
+    for (row, answer) in rows.iter().zip(&answers) {
+        let mut lines = answer.lines();
+
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}  {}",
+            row.day,
+            row.part,
+            lines.next().unwrap_or(""),
+            format!("{:.2?}", row.parse_time),
+            format!("{:.2?}", row.solve_time),
+            row.verdict.as_ref().map_or("-", |verdict| verdict.label()),
+            width = answer_width,
+        );
+
+        for line in lines {
+            println!("           {}", line);
+        }
+
+        total += row.solve_time;
+        if row.part == 1 {
+            total += row.parse_time;
+        }
+    }
+
+    println!("{}", "-".repeat(answer_width + 41));
+    println!("Total: {:.2?}", total);
+}
+
+/// Benchmarks every day with an input, or just the selected day, and writes the results to the
+/// output file, returning whether every day parsed.
+fn run_bench(opt: &Opt, runs: usize) -> Result<bool, Error> {
+    let mut benches = vec!();
+    let mut parsed = true;
+
+    if opt.all {
+        for day in solutions::DAYS {
+            let input = match read_input(&input_path(day.number)) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("skipping day {}: {}", day.number, err);
+                    continue;
+                },
+            };
+
+            match (day.bench)(&input, &Part::Both, runs, opt.warm_up) {
+                Ok(bench) => benches.push(bench),
+                Err(err) => {
+                    eprintln!("error: {}", Error::from(err));
+                    parsed = false;
+                },
+            }
+        }
+    } else {
+        let day = get_day(opt.day);
+        let input = read_input(&day_input_path(day, opt))?;
+
+        benches.push((day.bench)(&input, &opt.part, runs, opt.warm_up)?);
+    }
+
+    print_bench(&benches);
+
+    fs::write(&opt.bench_output, bench::to_json(&benches, runs, opt.warm_up))
+        .map_err(|err| Error::Output(opt.bench_output.clone(), err))?;
+
+    Ok(parsed)
+}
+
+fn print_bench(benches: &[Bench]) {
+    println!("Day  Stage   {:>10}  {:>10}  {:>10}", "Mean", "Median", "P95");
+    println!("{}", "-".repeat(47));
+
+    for bench in benches {
+        let parse = ("parse".to_string(), &bench.parse);
+        let parts = bench.parts
+            .iter()
+            .map(|(part, stats)| (format!("part {}", part), stats));
+
+        for (stage, stats) in std::iter::once(parse).chain(parts) {
+            println!(
+                "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}",
+                bench.day,
+                stage,
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+            );
+        }
+    }
+}
+
+fn get_day(number: Option<u8>) -> &'static Day {
+    match number {
+        Some(number) => solutions::get(number).unwrap_or_else(|| {
+            eprintln!("day {} is not implemented", number);
+            process::exit(1);
+        }),
+        None => latest_with_input(),
+    }
+}
+
+/// The latest day there's an input for, which is also the day `--test`'s example input is for.
+fn latest_with_input() -> &'static Day {
+    solutions::DAYS
+        .iter()
+        .rev()
+        .find(|day| fs::metadata(input_path(day.number)).is_ok())
+        .unwrap_or_else(solutions::latest)
+}
+
+fn day_input_path(day: &Day, opt: &Opt) -> String {
+    match &opt.input {
+        Some(path) => path.clone(),
+        None if opt.test => TEST_INPUT.to_string(),
+        None => input_path(day.number),
+    }
+}
 
 fn main() {
     let opt = Opt::from_args();
-    let input = if opt.test { TEST_INPUT } else { INPUT };
-    let input = fs::read_to_string(input).expect("oh no!");
 
-    let result = solve(&input);
-    println!("{}", result)
+    if let Some(threads) = opt.threads {
+        parallel::set_threads(threads);
+    }
+
+    if let Some(runs) = opt.bench {
+        if runs == 0 {
+            eprintln!("error: --bench needs at least one run");
+            process::exit(1);
+        }
+
+        match run_bench(&opt, runs) {
+            Ok(true) => (),
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            },
+        }
+        return;
+    }
+
+    if opt.all {
+        if !run_all(opt.verify, &opt.format) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let day = get_day(opt.day);
+
+    let passed = read_input(&day_input_path(day, &opt))
+        .and_then(|input| run_day(day, &input, &opt.part, opt.verify, &opt.format));
+
+    match passed {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        },
+    }
 }
@@ -0,0 +1,74 @@
+use std::{cmp::min, num::NonZeroUsize, sync::atomic::{AtomicUsize, Ordering}, thread};
+
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets how many threads `map` may use, where 0 means one per available core and 1 keeps
+/// everything on the calling thread.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+/// Maps `f` over `items` on a pool of worker threads, which take the next unclaimed item until
+/// there are none left. The results are in the same order as `items`.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_on(threads(), items, f)
+}
+
+fn map_on<T: Sync, R: Send>(threads: usize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = min(threads, items.len());
+
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| {
+                let mut done = vec!();
+
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+
+                    match items.get(idx) {
+                        Some(item) => done.push((idx, f(item))),
+                        None => break,
+                    }
+                }
+
+                done
+            }))
+            .collect();
+
+        for worker in workers {
+            for (idx, result) in worker.join().unwrap() {
+                results[idx] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let expected: Vec<usize> = items.iter().map(|item| item * item).collect();
+
+        assert_eq!(map_on(1, &items, |item| item * item), expected);
+        assert_eq!(map_on(4, &items, |item| item * item), expected);
+    }
+}
@@ -0,0 +1,363 @@
+use std::{cmp::max, fmt, ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign}};
+
+/// A position or offset in two dimensions. `y` counts down, so `Dir::Up` is `y - 1`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position or offset in three dimensions.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The four directions along the axes of a `Point`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// All eight directions around a `Point`, including diagonals.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The distance moving only along the axes.
+    pub fn manhattan(self, other: Point<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when diagonal moves count as one step.
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+}
+
+impl Point {
+    /// The point with each coordinate replaced by its sign, i.e. a single step towards it.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The four points next to this one along the axes.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Dir::ALL.iter().map(move |dir| self + dir.delta())
+    }
+
+    /// All eight points around this one.
+    pub fn adjacent(self) -> impl Iterator<Item = Point> {
+        Compass::ALL.iter().map(move |dir| self + dir.delta())
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        max(max(abs_diff(self.x, other.x), abs_diff(self.y, other.y)), abs_diff(self.z, other.z))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn dot(self, other: Point3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl Point3 {
+    /// Steps along each axis in both directions.
+    pub const ORTHOGONAL: [Point3; 6] = [
+        Point3::new(-1, 0, 0),
+        Point3::new(1, 0, 0),
+        Point3::new(0, -1, 0),
+        Point3::new(0, 1, 0),
+        Point3::new(0, 0, -1),
+        Point3::new(0, 0, 1),
+    ];
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Point3> {
+        Point3::ORTHOGONAL.iter().map(move |delta| self + *delta)
+    }
+}
+
+impl Dir {
+    /// Clockwise from up.
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    pub const fn delta(self) -> Point {
+        match self {
+            Dir::Up => Point::new(0, -1),
+            Dir::Right => Point::new(1, 0),
+            Dir::Down => Point::new(0, 1),
+            Dir::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir {
+        Dir::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir {
+        Dir::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Dir {
+        Dir::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Compass {
+    /// Clockwise from north.
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    pub const fn delta(self) -> Point {
+        match self {
+            Compass::N => Point::new(0, -1),
+            Compass::NE => Point::new(1, -1),
+            Compass::E => Point::new(1, 0),
+            Compass::SE => Point::new(1, 1),
+            Compass::S => Point::new(0, 1),
+            Compass::SW => Point::new(-1, 1),
+            Compass::W => Point::new(-1, 0),
+            Compass::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir> for Compass {
+    fn from(dir: Dir) -> Compass {
+        match dir {
+            Dir::Up => Compass::N,
+            Dir::Right => Compass::E,
+            Dir::Down => Compass::S,
+            Dir::Left => Compass::W,
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, n: T) -> Point<T> {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) {
+        *self = *self + other
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) {
+        *self = *self - other
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, n: T) -> Point3<T> {
+        Point3::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Point3<T>) {
+        *self = *self + other
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Point3<T>) {
+        *self = *self - other
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Point<T> {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Point3<T> {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> (T, T, T) {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl TryFrom<Point<isize>> for Point<usize> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point<isize>) -> Result<Point<usize>, Self::Error> {
+        Ok(Point::new(point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+impl TryFrom<Point<usize>> for Point<isize> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point<usize>) -> Result<Point<isize>, Self::Error> {
+        Ok(Point::new(point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(a + b, Point::new(2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(-a * 2, Point::new(-6, 4));
+        assert_eq!((a - b).signum(), Point::new(1, -1));
+        assert_eq!(Point3::new(1, 2, 3).dot(Point3::new(4, -5, 6)), 12);
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point::new(3, -2).manhattan(Point::new(-1, 4)), 10);
+        assert_eq!(Point::new(3, -2).chebyshev(Point::new(-1, 4)), 6);
+        assert_eq!(Point::<usize>::new(1, 5).manhattan(Point::new(4, 2)), 6);
+        assert_eq!(Point3::new(1, 1, 1).manhattan(Point3::new(2, 0, 4)), 5);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(Dir::Left.opposite().delta(), Point::new(1, 0));
+        assert_eq!(Compass::from(Dir::Down).delta(), Dir::Down.delta());
+        assert_eq!(Point::new(0, 0).adjacent().count(), 8);
+    }
+
+    #[test]
+    fn conversions() {
+        let point: Point = Point::new(2, 3);
+
+        assert_eq!(Point::<usize>::try_from(point), Ok(Point::new(2, 3)));
+        assert!(Point::<usize>::try_from(-point).is_err());
+        assert_eq!(<(isize, isize)>::from(point), (2, 3));
+    }
+}
@@ -0,0 +1,250 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, VecDeque}, hash::Hash};
+
+/// A route found by a search, from the start it left to the goal it reached, inclusive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+/// The nodes a search has reached, each with the one it was reached from, so that routes can be
+/// followed back to a start.
+struct Tree<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Tree<N> {
+    fn new() -> Tree<N> {
+        Tree {
+            nodes: vec!(),
+            parents: vec!(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// The index of `node`, adding it if it hasn't been reached before, along with whether it
+    /// was added.
+    fn reach(&mut self, node: N) -> (usize, bool) {
+        if let Some(idx) = self.index.get(&node) {
+            return (*idx, false);
+        }
+
+        self.index.insert(node.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.parents.push(None);
+
+        (self.nodes.len() - 1, true)
+    }
+
+    fn path(&self, mut idx: usize, cost: usize) -> Path<N> {
+        let mut nodes = vec!(self.nodes[idx].clone());
+
+        while let Some(parent) = self.parents[idx] {
+            nodes.push(self.nodes[parent].clone());
+            idx = parent;
+        }
+
+        nodes.reverse();
+
+        Path {
+            cost,
+            nodes,
+        }
+    }
+}
+
+/// Finds the fewest steps from any of `starts` to a node matching `is_goal`, where every move
+/// costs one.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = Tree::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let (idx, true) = tree.reach(start) {
+            queue.push_back((idx, 0));
+        }
+    }
+
+    while let Some((idx, cost)) = queue.pop_front() {
+        if is_goal(&tree.nodes[idx]) {
+            return Some(tree.path(idx, cost));
+        }
+
+        for next in successors(&tree.nodes[idx]) {
+            if let (next_idx, true) = tree.reach(next) {
+                tree.parents[next_idx] = Some(idx);
+                queue.push_back((next_idx, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the cheapest route from any of `starts` to a node matching `is_goal`, where
+/// `successors` gives each move along with its cost.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but exploring the nodes that look closest to a goal first. `heuristic` must
+/// never overestimate the remaining cost, or the route found may not be the cheapest.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut tree = Tree::new();
+    let mut costs: Vec<usize> = vec!();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let (idx, true) = tree.reach(start) {
+            costs.push(0);
+            heap.push(Reverse((heuristic(&tree.nodes[idx]), 0, idx)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        // a cheaper way here has already been explored
+        if cost > costs[idx] {
+            continue;
+        }
+
+        if is_goal(&tree.nodes[idx]) {
+            return Some(tree.path(idx, cost));
+        }
+
+        for (next, step) in successors(&tree.nodes[idx]) {
+            let next_cost = cost + step;
+            let (next_idx, is_new) = tree.reach(next);
+
+            if is_new {
+                costs.push(next_cost);
+            } else if next_cost < costs[next_idx] {
+                costs[next_idx] = next_cost;
+            } else {
+                continue;
+            }
+
+            tree.parents[next_idx] = Some(idx);
+            heap.push(Reverse((next_cost + heuristic(&tree.nodes[next_idx]), next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+/// The fewest steps to every node reachable from any of `starts`.
+pub fn distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ring of ten nodes where stepping forward costs 1 and stepping back costs 3.
+    fn ring(node: &usize) -> Vec<(usize, usize)> {
+        vec!(((node + 1) % 10, 1), ((node + 9) % 10, 3))
+    }
+
+    fn unweighted(node: &usize) -> Vec<usize> {
+        ring(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn bfs() {
+        let path = super::bfs([0], unweighted, |node| *node == 8).unwrap();
+
+        assert_eq!(path, Path { cost: 2, nodes: vec!(0, 9, 8) });
+    }
+
+    #[test]
+    fn multi_source() {
+        let path = super::bfs([2, 6], unweighted, |node| *node == 8).unwrap();
+
+        assert_eq!(path.nodes, vec!(6, 7, 8));
+        assert_eq!(super::bfs([0], |_| vec!(), |node| *node == 8), None);
+    }
+
+    #[test]
+    fn dijkstra() {
+        let path = super::dijkstra([0], ring, |node| *node == 8).unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes, vec!(0, 9, 8));
+        assert_eq!(super::dijkstra([0], ring, |node| *node == 7).unwrap().cost, 7);
+    }
+
+    #[test]
+    fn astar() {
+        for goal in 0..10 {
+            // the fewest steps either way round never costs more than going that way
+            let heuristic = |node: &usize| ((goal + 10 - node) % 10).min((node + 10 - goal) % 10);
+
+            let expected = super::dijkstra([3], ring, |node| *node == goal).unwrap();
+            let path = super::astar([3], ring, heuristic, |node| *node == goal).unwrap();
+
+            assert_eq!(path.cost, expected.cost);
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let distances = super::distances([0], unweighted);
+
+        assert_eq!(distances.len(), 10);
+        assert_eq!(distances[&5], 5);
+        assert_eq!(distances[&8], 2);
+    }
+}
@@ -0,0 +1,179 @@
+use std::{fmt, str::FromStr, time::{Duration, Instant}};
+use crate::error::ParseError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl Answer {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Number(_) => "number",
+            Answer::Text(_) => "text",
+            Answer::Unsolved => "unsolved",
+        }
+    }
+
+    /// The answer as a JSON value: a number, a string, or null if it's unsolved.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Number(number) => number.to_string(),
+            Answer::Text(text) => json_string(text),
+            Answer::Unsolved => "null".to_string(),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Answer {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(number: isize) -> Answer {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Answer {
+        Answer::Number(number as i64)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Answer {
+        answer.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Answer;
+
+    fn part_two(input: &Self::Input) -> Answer;
+}
+
+#[derive(Debug)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn numbers(&self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(part: &str) -> Result<Part, String> {
+        match part {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err(format!("expected 1, 2 or both, got {}", part)),
+        }
+    }
+}
+
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+pub struct Run {
+    pub parse_time: Duration,
+    pub results: Vec<PartResult>,
+}
+
+pub fn run<S: Solution>(input: &str, part: &Part) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let results = part.numbers()
+        .iter()
+        .map(|number| {
+            let start = Instant::now();
+            let answer = match number {
+                1 => S::part_one(&input),
+                _ => S::part_two(&input),
+            };
+
+            PartResult {
+                part: *number,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Run {
+        parse_time,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json() {
+        assert_eq!(Answer::Number(-12).to_json(), "-12");
+        assert_eq!(Answer::Text("#.\n\"a\"\\".to_string()).to_json(), "\"#.\\n\\\"a\\\"\\\\\"");
+        assert_eq!(Answer::Unsolved.to_json(), "null");
+    }
+}
@@ -1,28 +1,51 @@
-fn process(input: &str) -> Vec<usize> {
-    input
-        .split("\n\n")
-        .map(|elf| elf
-            .split_whitespace()
-            .map(|cals| cals
-                .parse::<usize>()
-                .unwrap()
-            )
-            .sum()
-        )
-        .collect()
+use crate::{error::{self, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(elves: &Vec<usize>) -> Answer {
+        solve_part_one(elves).into()
+    }
+
+    fn part_two(elves: &Vec<usize>) -> Answer {
+        solve(elves).into()
+    }
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let elves = process(input);
+fn process(input: &str) -> Result<Vec<usize>, ParseError> {
+    let mut elves = vec!(0);
+
+    for line in error::lines(input) {
+        let cals = line.text.trim();
+
+        if cals.is_empty() {
+            elves.push(0);
+        } else {
+            *elves.last_mut().unwrap() += line.parse::<usize>(cals)?;
+        }
+    }
+
+    Ok(elves)
+}
 
+pub fn solve_part_one(elves: &[usize]) -> usize {
     elves
-        .into_iter()
-        .reduce(|max, elf| std::cmp::max(max, elf))
+        .iter()
+        .copied()
+        .reduce(std::cmp::max)
         .unwrap()
 }
 
-pub fn solve(input: &str) -> usize {
-    let mut elves = process(input);
+pub fn solve(elves: &[usize]) -> usize {
+    let mut elves = elves.to_vec();
 
     elves.sort_by(|a, b| b.cmp(a));
     
@@ -31,3 +54,34 @@ pub fn solve(input: &str) -> usize {
         .take(3)
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day01::parse(EXAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day01::parse(EXAMPLE).unwrap()), 45000);
+    }
+}
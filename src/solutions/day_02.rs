@@ -1,4 +1,27 @@
-enum Shape {
+use crate::{error::{self, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<(Shape, Response)>;
+
+    fn parse(input: &str) -> Result<Vec<(Shape, Response)>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(rounds: &Vec<(Shape, Response)>) -> Answer {
+        solve_part_one(rounds).into()
+    }
+
+    fn part_two(rounds: &Vec<(Shape, Response)>) -> Answer {
+        solve(rounds).into()
+    }
+}
+
+#[derive(Debug)]
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
@@ -15,43 +38,83 @@ impl Shape {
 }
 
 trait ToShape {
-    fn to_shape(&self) -> Shape;
+    fn to_shape(&self) -> Option<Shape>;
+}
+
+impl ToShape for &str {
+    fn to_shape(&self) -> Option<Shape> {
+        match *self {
+            "A" => Some(Shape::Rock),
+            "B" => Some(Shape::Paper),
+            "C" => Some(Shape::Scissors),
+            _ => None,
+        }
+    }
 }
 
-impl ToShape for char {
+/// The second column of the strategy guide: a shape in part one, an outcome in part two.
+#[derive(Debug)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+impl Response {
     fn to_shape(&self) -> Shape {
         match self {
-            'A' => Shape::Rock,
-            'B' => Shape::Paper,
-            'C' => Shape::Scissors,
-            'X' => Shape::Rock,
-            'Y' => Shape::Paper,
-            'Z' => Shape::Scissors,
-            _ => panic!("bad char")
+            Response::X => Shape::Rock,
+            Response::Y => Shape::Paper,
+            Response::Z => Shape::Scissors,
+        }
+    }
+
+    fn to_outcome(&self) -> Outcome {
+        match self {
+            Response::X => Outcome::Lose,
+            Response::Y => Outcome::Draw,
+            Response::Z => Outcome::Win,
         }
     }
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let rounds = process_part_one(input);
+trait ToResponse {
+    fn to_response(&self) -> Option<Response>;
+}
 
-    rounds
-        .iter()
-        .fold(0, |score, (opponent, us)| {
-            score + us.get_value() + get_outcome(opponent, us)
-        })
+impl ToResponse for &str {
+    fn to_response(&self) -> Option<Response> {
+        match *self {
+            "X" => Some(Response::X),
+            "Y" => Some(Response::Y),
+            "Z" => Some(Response::Z),
+            _ => None,
+        }
+    }
 }
 
-fn process_part_one(input: &str) -> Vec<(Shape, Shape)> {
-    input
-        .lines()
-        .map(|line| (
-            line.chars().nth(0).unwrap().to_shape(),
-            line.chars().nth(2).unwrap().to_shape(),
-        ))
+fn process(input: &str) -> Result<Vec<(Shape, Response)>, ParseError> {
+    error::lines(input)
+        .map(|line| {
+            let (opponent, response) = line.split_once(line.text, " ")?;
+
+            Ok((
+                opponent.to_shape().ok_or_else(|| line.error(opponent, "A, B or C"))?,
+                response.to_response().ok_or_else(|| line.error(response, "X, Y or Z"))?,
+            ))
+        })
         .collect()
 }
 
+pub fn solve_part_one(rounds: &[(Shape, Response)]) -> usize {
+    rounds
+        .iter()
+        .fold(0, |score, (opponent, response)| {
+            let us = response.to_shape();
+            score + us.get_value() + get_outcome(opponent, &us)
+        })
+}
+
 fn get_outcome(opponent: &Shape, us: &Shape) -> usize {
     match opponent {
         Shape::Rock => match us {
@@ -79,42 +142,15 @@ enum Outcome {
     Win,
 }
 
-trait ToOutcome {
-    fn to_outcome(&self) -> Outcome;
-}
-
-impl ToOutcome for char {
-    fn to_outcome(&self) -> Outcome {
-        match self {
-            'X' => Outcome::Lose,
-            'Y' => Outcome::Draw,
-            'Z' => Outcome::Win,
-            _ => panic!("bad char")
-        }
-    }
-}
-
-pub fn solve(input: &str) -> usize {
-    let rounds = process(input);
-
+pub fn solve(rounds: &[(Shape, Response)]) -> usize {
     rounds
         .iter()
-        .fold(0, |score, (opponent, outcome)| {
-            let us = get_shape(opponent, outcome);
+        .fold(0, |score, (opponent, response)| {
+            let us = get_shape(opponent, &response.to_outcome());
             score + us.get_value() + get_outcome(opponent, &us)
         })
 }
 
-fn process(input: &str) -> Vec<(Shape, Outcome)> {
-    input
-        .lines()
-        .map(|line| (
-            line.chars().nth(0).unwrap().to_shape(),
-            line.chars().nth(2).unwrap().to_outcome(),
-        ))
-        .collect()
-}
-
 fn get_shape(opponent: &Shape, outcome: &Outcome) -> Shape {
     match opponent {
         Shape::Rock => match outcome {
@@ -134,3 +170,31 @@ fn get_shape(opponent: &Shape, outcome: &Outcome) -> Shape {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+A Y
+B X
+C Z";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day02::parse(EXAMPLE).unwrap()), 15);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day02::parse(EXAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn bad_shape() {
+        let err = Day02::parse("A Y\nD X").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (2, 2, 1));
+        assert_eq!(err.text, "D");
+    }
+}
@@ -1,4 +1,25 @@
 use itertools::Itertools;
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(rucksacks: &Vec<String>) -> Answer {
+        solve_part_one(rucksacks).into()
+    }
+
+    fn part_two(rucksacks: &Vec<String>) -> Answer {
+        solve(rucksacks).into()
+    }
+}
 
 trait ToValue {
     fn to_value(&self) -> u32;
@@ -14,17 +35,66 @@ impl ToValue for char {
     }
 }
 
-fn process(input: &str) -> Vec<(&str, &str)> {
-    input
-        .lines()
+fn process(input: &str) -> Result<Vec<String>, ParseError> {
+    let lines: Vec<Line> = error::lines(input).collect();
+
+    let rucksacks: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let bad_item = line.text
+                .char_indices()
+                .find(|(_idx, item)| !item.is_ascii_alphabetic());
+
+            if let Some((idx, item)) = bad_item {
+                return Err(line.error(&line.text[idx..idx + item.len_utf8()], "an item letter"));
+            }
+
+            if line.text.is_empty() || line.text.len() % 2 != 0 {
+                return Err(line.invalid("an even number of items"));
+            }
+
+            let (compartment_a, compartment_b) = line.text.split_at(line.text.len() / 2);
+
+            if !compartment_a.chars().any(|item| compartment_b.contains(item)) {
+                return Err(line.invalid("an item in both compartments"));
+            }
+
+            Ok(line.text.to_string())
+        })
+        .collect::<Result<_, _>>()?;
+
+    if rucksacks.is_empty() {
+        return Err(error::end_of_input(input, "a rucksack"));
+    }
+
+    for (group, lines) in rucksacks.chunks(3).zip(lines.chunks(3)) {
+        let [backpack_a, backpack_b, backpack_c] = group else {
+            return Err(error::end_of_input(input, "the rest of the group of three"));
+        };
+
+        let has_badge = backpack_a
+            .chars()
+            .any(|item| backpack_b.contains(item) && backpack_c.contains(item));
+
+        if !has_badge {
+            return Err(lines[2].invalid("a badge shared with the two rucksacks before"));
+        }
+    }
+
+    Ok(rucksacks)
+}
+
+fn get_compartments(rucksacks: &[String]) -> Vec<(&str, &str)> {
+    rucksacks
+        .iter()
         .map(|rucksack|
             rucksack.split_at(rucksack.len() / 2)
         )
         .collect()
 }
 
-pub fn solve_part_one(input: &str) -> u32 {
-    process(input)
+pub fn solve_part_one(rucksacks: &[String]) -> u32 {
+    get_compartments(rucksacks)
         .into_iter()
         .fold(0, |sum, (compartment_a, compartment_b)| {
             sum + compartment_a
@@ -37,9 +107,9 @@ pub fn solve_part_one(input: &str) -> u32 {
         })
 }
 
-pub fn solve(input: &str) -> u32 {
-    input
-        .lines()
+pub fn solve(rucksacks: &[String]) -> u32 {
+    rucksacks
+        .iter()
         .tuples()
         .fold(0, |sum, (backpack_a, backpack_b, backpack_c)| {
             sum + backpack_a
@@ -51,3 +121,34 @@ pub fn solve(input: &str) -> u32 {
                 .to_value()
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day03::parse(EXAMPLE).unwrap()), 157);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day03::parse(EXAMPLE).unwrap()), 70);
+    }
+
+    #[test]
+    fn unsolvable() {
+        assert_eq!(Day03::parse("").unwrap_err().line, 1);
+        assert_eq!(Day03::parse("vJrwpWtwJgWrhcsFMMfFFhFp\n\n").unwrap_err().line, 2);
+        assert_eq!(Day03::parse("abcd").unwrap_err().line, 1);
+        assert_eq!(Day03::parse(&EXAMPLE[..EXAMPLE.rfind('\n').unwrap()]).unwrap_err().line, 6);
+    }
+}
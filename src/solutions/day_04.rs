@@ -1,24 +1,43 @@
-use itertools::Itertools;
-
-fn process(input: &str) -> Vec<((usize, usize), (usize, usize))> {
-    input
-        .lines()
-        .map(|pair| pair
-            .split(',')
-            .map(|elf| elf
-                .split('-')
-                .map(|section| section
-                    .parse().unwrap()
-                )
-                .collect_tuple().unwrap()
-            )
-            .collect_tuple().unwrap()
-        )
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
+
+pub type Range = (usize, usize);
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(pairs: &Vec<(Range, Range)>) -> Answer {
+        solve_part_one(pairs).into()
+    }
+
+    fn part_two(pairs: &Vec<(Range, Range)>) -> Answer {
+        solve(pairs).into()
+    }
+}
+
+fn process(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    error::lines(input)
+        .map(|line| {
+            let (elf_a, elf_b) = line.split_once(line.text, ",")?;
+            Ok((get_range(&line, elf_a)?, get_range(&line, elf_b)?))
+        })
         .collect()
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    process(input)
+fn get_range(line: &Line, elf: &str) -> Result<Range, ParseError> {
+    let (start, end) = line.split_once(elf, "-")?;
+    Ok((line.parse(start)?, line.parse(end)?))
+}
+
+pub fn solve_part_one(pairs: &[(Range, Range)]) -> usize {
+    pairs
         .iter()
         .filter(|(a, b)|
             contains(a, b)
@@ -26,14 +45,14 @@ pub fn solve_part_one(input: &str) -> usize {
         .count()
 }
 
-fn contains(a: &(usize, usize), b: &(usize, usize)) -> bool {
+fn contains(a: &Range, b: &Range) -> bool {
     let a_contains_b = a.0 <= b.0 && a.1 >= b.1;
     let b_contains_a = b.0 <= a.0 && b.1 >= a.1;
     a_contains_b || b_contains_a
 }
 
-pub fn solve(input: &str) -> usize {
-    process(input)
+pub fn solve(pairs: &[(Range, Range)]) -> usize {
+    pairs
         .iter()
         .filter(|(a, b)|
             overlaps(a, b)
@@ -41,8 +60,31 @@ pub fn solve(input: &str) -> usize {
         .count()
 }
 
-fn overlaps(a: &(usize, usize), b: &(usize, usize)) -> bool {
+fn overlaps(a: &Range, b: &Range) -> bool {
     let a_starts_in_b = a.0 >= b.0 && a.0 <= b.1;
     let a_ends_in_b = a.1 >= b.0 && a.0 <= b.1;
     a_starts_in_b || a_ends_in_b
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day04::parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day04::parse(EXAMPLE).unwrap()), 4);
+    }
+}
@@ -1,40 +1,80 @@
 use itertools::Itertools;
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
 
-type Stack = Vec<char>;
+pub type Stack = Vec<char>;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = (Vec<Stack>, Vec<Step>);
+
+    fn parse(input: &str) -> Result<(Vec<Stack>, Vec<Step>), ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one((stacks, steps): &(Vec<Stack>, Vec<Step>)) -> Answer {
+        solve_part_one(stacks, steps).into()
+    }
+
+    fn part_two((stacks, steps): &(Vec<Stack>, Vec<Step>)) -> Answer {
+        solve(stacks, steps).into()
+    }
+}
 
 #[derive(Debug)]
-struct Step {
-    n: usize,
-    from: usize,
-    to: usize,
+pub struct Step {
+    pub n: usize,
+    pub from: usize,
+    pub to: usize,
 }
 
 trait ToStep {
-    fn to_step(&self) -> Step;
+    fn to_step(&self, n_stacks: usize) -> Result<Step, ParseError>;
+}
+
+impl ToStep for Line<'_> {
+    fn to_step(&self, n_stacks: usize) -> Result<Step, ParseError> {
+        let parts: Vec<&str> = self.text.split_whitespace().collect();
+
+        let (n, from, to) = match parts[..] {
+            ["move", n, "from", from, "to", to] => (n, from, to),
+            _ => return Err(self.invalid("a step like \"move 1 from 2 to 3\"")),
+        };
+
+        Ok(Step {
+            n: self.parse(n)?,
+            from: get_stack(self, from, n_stacks)?,
+            to: get_stack(self, to, n_stacks)?,
+        })
+    }
 }
 
-impl ToStep for &str {
-    fn to_step(&self) -> Step {
-        let parts: Vec<&str> = self.split_whitespace().collect();
-        Step {
-            n: parts.get(1).unwrap().parse().unwrap(),
-            from: parts.get(3).unwrap().parse::<usize>().unwrap() - 1,
-            to: parts.get(5).unwrap().parse::<usize>().unwrap() - 1,
-        }
+fn get_stack(line: &Line, stack: &str, n_stacks: usize) -> Result<usize, ParseError> {
+    match line.parse::<usize>(stack)? {
+        n if n >= 1 && n <= n_stacks => Ok(n - 1),
+        _ => Err(line.error(stack, &format!("a stack from 1 to {}", n_stacks))),
     }
 }
 
-fn process(input: &str) -> (Vec<Stack>, Vec<Step>) {
-    let (stack_rows, steps) = input
-        .split("\n\n")
-        .collect_tuple()
-        .unwrap();
+fn process(input: &str) -> Result<(Vec<Stack>, Vec<Step>), ParseError> {
+    let lines: Vec<Line> = error::lines(input).collect();
 
-    let mut stack_rows: Vec<&str> = stack_rows.lines().collect();
-    stack_rows.pop();
+    let split = lines
+        .iter()
+        .position(|line| line.text.is_empty())
+        .ok_or_else(|| error::end_of_input(input, "a blank line after the stacks"))?;
+
+    let (stack_rows, steps) = lines.split_at(split);
+
+    let mut stack_rows = stack_rows.to_vec();
+    let labels = stack_rows
+        .pop()
+        .ok_or_else(|| lines[split].invalid("a drawing of the stacks"))?;
     stack_rows.reverse();
 
-    let n_stacks = (stack_rows.first().unwrap().len() + 1) / 4;
+    let n_stacks = labels.text.split_whitespace().count();
 
     let mut stacks = vec![Vec::new(); n_stacks];
 
@@ -43,30 +83,48 @@ fn process(input: &str) -> (Vec<Stack>, Vec<Step>) {
         stack_rows
             .iter()
             .for_each(|stack_row| {
-                let item = stack_row.chars().nth(idx).unwrap();
-                if item != ' ' {
-                    stacks.get_mut(n).unwrap().push(item);
+                if let Some(item) = stack_row.text.chars().nth(idx) {
+                    if item != ' ' {
+                        stacks.get_mut(n).unwrap().push(item);
+                    }
                 }
             });
     }
 
+    // both crane models move the same number of crates, so the heights don't depend on the part
+    let mut heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+
     let steps = steps
-        .lines()
-        .map(|step_line| step_line.to_step())
-        .collect();
+        .iter()
+        .skip(1)
+        .map(|step_line| {
+            let step = step_line.to_step(n_stacks)?;
+
+            if step.n > heights[step.from] {
+                let n = step_line.text.split_whitespace().nth(1).unwrap();
+                let expected = format!("at most the {} crates on the stack", heights[step.from]);
+                return Err(step_line.error(n, &expected));
+            }
+
+            heights[step.from] -= step.n;
+            heights[step.to] += step.n;
+
+            Ok(step)
+        })
+        .collect::<Result<_, _>>()?;
 
-    (stacks, steps)
+    Ok((stacks, steps))
 }
 
-pub fn solve_part_one(input: &str) -> String {
-    let (mut stacks, steps) = process(input);
+pub fn solve_part_one(stacks: &[Stack], steps: &[Step]) -> String {
+    let mut stacks = stacks.to_vec();
 
     steps
         .iter()
         .for_each(|step| {
             for _i in 0..step.n {
                 let source = stacks.get_mut(step.from).unwrap();
-                let item = source.pop().expect("stack is empty!");
+                let item = source.pop().expect("the stack heights are checked while parsing");
                 let dest = stacks.get_mut(step.to).unwrap();
                 dest.push(item);
             }
@@ -85,8 +143,8 @@ fn top_items(stacks: Vec<Stack>) -> String {
         .join("")
 }
 
-pub fn solve(input: &str) -> String {
-    let (mut stacks, steps) = process(input);
+pub fn solve(stacks: &[Stack], steps: &[Step]) -> String {
+    let mut stacks = stacks.to_vec();
 
     steps
         .iter()
@@ -95,7 +153,7 @@ pub fn solve(input: &str) -> String {
             let source = stacks.get_mut(step.from).unwrap();
 
             for _i in 0..step.n {
-                items.push(source.pop().expect("stack is empty!"));
+                items.push(source.pop().expect("the stack heights are checked while parsing"));
             }
             items.reverse();
 
@@ -106,3 +164,39 @@ pub fn solve(input: &str) -> String {
     
     top_items(stacks)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn part_one() {
+        let (stacks, steps) = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(solve_part_one(&stacks, &steps), "CMZ");
+    }
+
+    #[test]
+    fn part_two() {
+        let (stacks, steps) = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&stacks, &steps), "MCD");
+    }
+
+    #[test]
+    fn too_many_crates() {
+        let err = Day05::parse(&EXAMPLE.replace("move 3 from 1", "move 4 from 1")).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (7, 6, "4"));
+    }
+}
@@ -1,8 +1,29 @@
 use itertools::Itertools;
+use crate::{error::ParseError, solution::{Answer, Solution}};
 
 const START_OF_PACKET: usize = 4;
 const START_OF_MESSAGE: usize = 14;
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<char>;
+
+    fn parse(input: &str) -> Result<Vec<char>, ParseError> {
+        Ok(input.trim().chars().collect())
+    }
+
+    fn part_one(signal: &Vec<char>) -> Answer {
+        solve_part_one(signal).into()
+    }
+
+    fn part_two(signal: &Vec<char>) -> Answer {
+        solve(signal).into()
+    }
+}
+
 trait IsMarker {
     fn is_marker(&self) -> bool;
 }
@@ -13,21 +34,41 @@ impl IsMarker for &&[char] {
     }
 }
 
-fn find_marker(input: &str, marker_len: usize) -> usize {
-    input
-        .trim()
-        .chars()
-        .collect::<Vec<char>>()
+fn find_marker(signal: &[char], marker_len: usize) -> Option<usize> {
+    signal
         .windows(marker_len)
         .find_position(|window| window.is_marker())
-        .expect("no marker found!")
-        .0 + marker_len
+        .map(|(idx, _window)| idx + marker_len)
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    find_marker(input, START_OF_PACKET)
+pub fn solve_part_one(signal: &[char]) -> Option<usize> {
+    find_marker(signal, START_OF_PACKET)
 }
 
-pub fn solve(input: &str) -> usize {
-    find_marker(input, START_OF_MESSAGE)
+pub fn solve(signal: &[char]) -> Option<usize> {
+    find_marker(signal, START_OF_MESSAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day06::parse(EXAMPLE).unwrap()), Some(7));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day06::parse(EXAMPLE).unwrap()), Some(19));
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(Day06::part_one(&Day06::parse("abc").unwrap()), Answer::Unsolved);
+        assert_eq!(Day06::part_two(&Day06::parse("abcdabcdabcdabcd").unwrap()), Answer::Unsolved);
+    }
 }
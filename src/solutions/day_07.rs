@@ -1,5 +1,5 @@
-use itertools::Itertools;
 use std::{collections::HashMap, cmp::min};
+use crate::{error::{self, ParseError}, solution::{Answer, Solution}};
 
 const ROOT: &str = "ROOT";
 
@@ -9,12 +9,32 @@ const TOTAL: usize = 70_000_000;
 const NEEDED: usize = 30_000_000;
 const TARGET: usize = TOTAL - NEEDED;
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = HashMap<String, usize>;
+
+    fn parse(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(dirs: &HashMap<String, usize>) -> Answer {
+        solve_part_one(dirs).into()
+    }
+
+    fn part_two(dirs: &HashMap<String, usize>) -> Answer {
+        solve(dirs).into()
+    }
+}
+
 trait Dirs {
-    fn add_size(&mut self, size: usize, stack: &Vec<String>);
+    fn add_size(&mut self, size: usize, stack: &[String]);
 }
 
 impl Dirs for HashMap<String, usize> {
-    fn add_size(&mut self, size: usize, stack: &Vec<String>) {
+    fn add_size(&mut self, size: usize, stack: &[String]) {
         stack
             .iter()
             .fold("".to_string(), |path, dir| {
@@ -29,36 +49,34 @@ impl Dirs for HashMap<String, usize> {
     }
 }
 
-fn process(input: &str) -> HashMap<String, usize> {
+fn process(input: &str) -> Result<HashMap<String, usize>, ParseError> {
     let mut stack = vec!(ROOT.to_string());
 
-    let mut dirs: HashMap<String, usize> = HashMap::new();
+    // the root is there even if nothing is listed in it
+    let mut dirs: HashMap<String, usize> = HashMap::from([(format!("/{}", ROOT), 0)]);
 
-    for line in input.lines().skip(1) {
-        if line.starts_with("$ cd ") {
-            let dir = line.replace("$ cd ", "");
+    for line in error::lines(input).skip(1) {
+        if let Some(dir) = line.text.strip_prefix("$ cd ") {
             if dir == ".." {
+                if stack.len() == 1 {
+                    return Err(line.error(dir, "a directory below the root to leave"));
+                }
+
                 stack.pop();
             } else {
-                stack.push(dir);
+                stack.push(dir.to_string());
             }
-        } else if line.starts_with("$ ls") {
-
-        } else if line.starts_with("dir ") {
-
-        } else {
-            let (size, _name) = line.split_whitespace().collect_tuple().unwrap();
-            let size = size.parse().unwrap();
+        } else if line.text != "$ ls" && !line.text.starts_with("dir ") {
+            let (size, _name) = line.split_once(line.text, " ")?;
+            let size = line.parse(size)?;
             dirs.add_size(size, &stack);
         }
     }
 
-    dirs
+    Ok(dirs)
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let dirs = process(input);
-
+pub fn solve_part_one(dirs: &HashMap<String, usize>) -> usize {
     dirs.iter()
         .fold(0, |sum, (_dir, size)| {
             if size <= &LIMIT {
@@ -69,15 +87,13 @@ pub fn solve_part_one(input: &str) -> usize {
         })
 }
 
-pub fn solve(input: &str) -> usize {
-    let dirs = process(input);
-
+pub fn solve(dirs: &HashMap<String, usize>) -> usize {
     let (_root, used) = dirs
         .iter()
         .find(|(dir, _size)|
             **dir == format!("/{}", ROOT)
         )
-        .unwrap();
+        .expect("the root is added while parsing");
 
     let mut smallest: Option<usize> = None;
 
@@ -91,5 +107,52 @@ pub fn solve(input: &str) -> usize {
             }
         });
 
-    smallest.unwrap()
+    // the root always qualifies, since deleting it leaves nothing used
+    smallest.expect("the root is added while parsing")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day07::parse(EXAMPLE).unwrap()), 95437);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day07::parse(EXAMPLE).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn leaving_the_root() {
+        assert_eq!(solve(&Day07::parse("").unwrap()), 0);
+        assert_eq!(Day07::parse("$ cd /\n$ cd ..").unwrap_err().line, 2);
+    }
 }
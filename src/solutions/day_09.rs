@@ -1,154 +1,147 @@
 use std::collections::HashSet;
-use itertools::Itertools;
+use crate::{error::{self, ParseError}, point::{Dir, Point}, solution::{Answer, Solution}};
 
 const KNOTS: usize = 10;
 
-#[derive(Clone)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
+pub struct Day09;
 
-impl Dir {
-    fn get_delta(&self) -> Pos {
-        match self {
-            Dir::Left => Pos { x: -1, y: 0 },
-            Dir::Right => Pos { x: 1, y: 0 },
-            Dir::Up => Pos { x: 0, y: 1 },
-            Dir::Down => Pos { x: 0, y: -1 },
-        }
-    }
-}
+impl Solution for Day09 {
+    const DAY: u8 = 9;
 
-#[derive(Hash, Eq, PartialEq, Clone, Debug)]
-struct Pos {
-    x: isize,
-    y: isize,
-}
+    type Input = Vec<Motion>;
 
-impl Pos {
-    fn new() -> Pos {
-        Pos { x: 0, y: 0 }
+    fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
     }
 
-    fn follow(&mut self, head: &Pos) {
-        let mut d = Pos::new();
-
-        if head.x >= self.x + 2 {
-            d.x = 1;
-
-            if head.y > self.y {
-                d.y = 1;
-            } else if head.y < self.y {
-                d.y = -1;
-            }
-        } else if head.x <= self.x - 2 {
-            d.x = -1;
-
-            if head.y > self.y {
-                d.y = 1;
-            } else if head.y < self.y {
-                d.y = -1;
-            }
-        }
+    fn part_one(motions: &Vec<Motion>) -> Answer {
+        solve_part_one(motions).into()
+    }
 
-        if head.y >= self.y + 2 {
-            d.y = 1;
+    fn part_two(motions: &Vec<Motion>) -> Answer {
+        solve(motions).into()
+    }
+}
 
-            if head.x > self.x {
-                d.x = 1;
-            } else if head.x < self.x {
-                d.x = -1;
-            }
-        } else if head.y <= self.y - 2 {
-            d.y = -1;
+/// A direction and the number of steps to take in it.
+pub type Motion = (Dir, usize);
 
-            if head.x > self.x {
-                d.x = 1;
-            } else if head.x < self.x {
-                d.x = -1;
-            }
-        }
-
-        *self += d;
-    }
+trait Follow {
+    fn follow(&mut self, head: &Point);
 }
 
-impl std::ops::AddAssign for Pos {
-    fn add_assign(&mut self, other: Pos) {
-        *self = Pos {
-            x: self.x + other.x,
-            y: self.y + other.y,
+impl Follow for Point {
+    /// Steps towards `head`, diagonally if needed, once it's no longer touching.
+    fn follow(&mut self, head: &Point) {
+        if self.chebyshev(*head) > 1 {
+            *self += (*head - *self).signum();
         }
     }
 }
 
-fn process(input: &str) -> Vec<Dir> {
-    input
-        .lines()
-        .flat_map(|line| {
-            let (dir, n) = line.split_whitespace().collect_tuple().unwrap();
+fn process(input: &str) -> Result<Vec<Motion>, ParseError> {
+    error::lines(input)
+        .map(|line| {
+            let (dir, n) = line.split_once(line.text, " ")?;
 
             let dir = match dir {
                 "L" => Dir::Left,
                 "R" => Dir::Right,
                 "U" => Dir::Up,
                 "D" => Dir::Down,
-                _ => panic!("bad direction!"),
+                _ => return Err(line.error(dir, "L, R, U or D")),
             };
 
-            let n = n.parse().unwrap();
-
-            vec![dir; n]
+            Ok((dir, line.parse(n)?))
         })
         .collect()
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let motions = process(input);
-
-    let mut head = Pos::new();
-    let mut tail = Pos::new();
-    let mut visited: HashSet<Pos> = HashSet::new();
+pub fn solve_part_one(motions: &[Motion]) -> usize {
+    let mut head = Point::default();
+    let mut tail = Point::default();
+    let mut visited: HashSet<Point> = HashSet::new();
 
-    visited.insert(tail.clone());
+    visited.insert(tail);
 
     motions
         .iter()
-        .for_each(|motion| {
-            let d = motion.get_delta();
-            head += d;
-            tail.follow(&head);
-            visited.insert(tail.clone());
+        .for_each(|(dir, n)| {
+            let d = dir.delta();
+
+            for _step in 0..*n {
+                head += d;
+                tail.follow(&head);
+                visited.insert(tail);
+            }
         });
 
     visited.len()
 }
 
-pub fn solve(input: &str) -> usize {
-    let motions = process(input);
+pub fn solve(motions: &[Motion]) -> usize {
+    let mut knots = [Point::default(); KNOTS];
+    let mut visited: HashSet<Point> = HashSet::new();
 
-    let mut knots = vec![Pos::new(); KNOTS];
-    let mut visited: HashSet<Pos> = HashSet::new();
-
-    visited.insert(knots.last().unwrap().clone());
+    visited.insert(*knots.last().unwrap());
 
     motions
         .iter()
-        .for_each(|motion| {
-            let d = motion.get_delta();
+        .for_each(|(dir, n)| {
+            let d = dir.delta();
 
-            *knots.first_mut().unwrap() += d;
+            for _step in 0..*n {
+                *knots.first_mut().unwrap() += d;
 
-            for i in 1..KNOTS {
-                let head = knots.get(i - 1).unwrap().clone();
-                knots.get_mut(i).unwrap().follow(&head);
-            }
+                for i in 1..KNOTS {
+                    let head = *knots.get(i - 1).unwrap();
+                    knots.get_mut(i).unwrap().follow(&head);
+                }
 
-            visited.insert(knots.last().unwrap().clone());
+                visited.insert(*knots.last().unwrap());
+            }
         });
 
     visited.len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    const LARGER_EXAMPLE: &str = "\
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day09::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day09::parse(EXAMPLE).unwrap()), 1);
+        assert_eq!(solve(&Day09::parse(LARGER_EXAMPLE).unwrap()), 36);
+    }
+
+    #[test]
+    fn long_motion() {
+        assert_eq!(Day09::parse("R 18446744073709551615").unwrap(), vec!((Dir::Right, usize::MAX)));
+    }
+}
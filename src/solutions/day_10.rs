@@ -1,23 +1,42 @@
-enum Instruction {
+use crate::{error::{self, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(instructions: &Vec<Instruction>) -> Answer {
+        solve_part_one(instructions).into()
+    }
+
+    fn part_two(instructions: &Vec<Instruction>) -> Answer {
+        solve(instructions).into()
+    }
+}
+
+pub enum Instruction {
     Addx(isize),
     Noop,
 }
 
-fn process(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|line| match line {
-            "noop" => Instruction::Noop,
-            _ => Instruction::Addx(
-                line.replace("addx ", "").parse().unwrap()
-            ),
+fn process(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    error::lines(input)
+        .map(|line| match line.text {
+            "noop" => Ok(Instruction::Noop),
+            _ => Ok(Instruction::Addx(
+                line.parse(line.strip_prefix(line.text, "addx ")?)?
+            )),
         })
         .collect()
 }
 
-pub fn solve_part_one(input:  &str) -> isize {
-    let instructions = process(input);
-
+pub fn solve_part_one(instructions: &[Instruction]) -> isize {
     let mut cycle = 1;
     let mut i_ptr = 0;
     let mut timer: Option<usize> = None;
@@ -52,9 +71,7 @@ pub fn solve_part_one(input:  &str) -> isize {
     sum
 }
 
-pub fn solve(input:  &str) -> String {
-    let instructions = process(input);
-
+pub fn solve(instructions: &[Instruction]) -> String {
     let mut cycle = 0;
     let mut i_ptr = 0;
     let mut timer: Option<usize> = None;
@@ -63,8 +80,9 @@ pub fn solve(input:  &str) -> String {
     let mut screen = [false; 240];
 
     while let Some(instruction) = instructions.get(i_ptr) {
-        if cycle >= x - 1 && cycle <= x + 1 {
-            screen[(cycle) as usize] = true;
+        // the screen only shows the first 240 cycles of a longer program
+        if let Some(pixel) = screen.get_mut(cycle as usize) {
+            *pixel = cycle >= x - 1 && cycle <= x + 1;
         }
 
         if let Instruction::Addx(value) = instruction {
@@ -109,3 +127,182 @@ fn get_output(screen: [bool; 240]) -> String {
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    const SCREEN: &str = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day10::parse(EXAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day10::parse(EXAMPLE).unwrap()), SCREEN);
+    }
+
+    #[test]
+    fn long_program() {
+        let program = format!("{}\nnoop", EXAMPLE);
+
+        assert_eq!(solve(&Day10::parse(&program).unwrap()), SCREEN);
+    }
+}
@@ -1,21 +1,44 @@
-use itertools::Itertools;
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
 
-enum Operand {
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(monkeys: &Vec<Monkey>) -> Answer {
+        solve_part_one(monkeys).into()
+    }
+
+    fn part_two(monkeys: &Vec<Monkey>) -> Answer {
+        solve(monkeys).into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Operand {
     Num(usize),
     Old,
 }
 
-enum Op {
+#[derive(Clone, Debug)]
+pub enum Op {
     Add(Operand),
     Multiply(Operand),
 }
 
-struct Monkey {
-    items: Vec<usize>,
-    op: Op,
-    test: usize,
-    if_true: usize,
-    if_false: usize,
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    pub items: Vec<usize>,
+    pub op: Op,
+    pub test: usize,
+    pub if_true: usize,
+    pub if_false: usize,
     inspections: usize,
 }
 
@@ -30,25 +53,37 @@ const LABELS: [&str; 5] = [
 const ROUNDS: usize = 20;
 const ROUNDS_TWO: usize = 10_000;
 
-fn get_items(line: &str) -> Vec<usize> {
-    line.split(", ").map(|item| item.parse().unwrap()).collect()
+fn get_items(line: &Line) -> Result<Vec<usize>, ParseError> {
+    line.strip_prefix(line.text, LABELS[0])?
+        .split(", ")
+        .map(|item| line.parse(item))
+        .collect()
 }
 
-fn get_op(line: &str) -> Op {
-    let (op, operand) = line.split_whitespace().collect_tuple().unwrap();
+fn get_op(line: &Line) -> Result<Op, ParseError> {
+    let (op, operand) = line.split_once(line.strip_prefix(line.text, LABELS[1])?, " ")?;
 
     let operand = match operand {
         "old" => Operand::Old,
-        _ => Operand::Num(operand.parse().unwrap()),
+        _ => Operand::Num(line.parse(operand)?),
     };
 
     match op {
-        "+" => Op::Add(operand),
-        "*" => Op::Multiply(operand),
-        _ => panic!("bad operator!"),
+        "+" => Ok(Op::Add(operand)),
+        "*" => Ok(Op::Multiply(operand)),
+        _ => Err(line.error(op, "+ or *")),
     }
 }
 
+fn get_number(line: &Line, label: &str) -> Result<usize, ParseError> {
+    line.parse(line.strip_prefix(line.text, label)?)
+}
+
+/// The number at the end of a line of notes.
+fn line_value<'a>(line: &Line<'a>) -> &'a str {
+    line.text.rsplit(' ').next().unwrap()
+}
+
 fn inspect(item: usize, op: &Op) -> usize {
     match op {
         Op::Add(operand) => item + match operand {
@@ -62,31 +97,52 @@ fn inspect(item: usize, op: &Op) -> usize {
     }
 }
 
-fn process(input: &str) -> Vec<Monkey> {
-    input
-        .split("\n\n")
-        .map(|monkey| {
-            let mut monkey = monkey.to_string();
-            for label in LABELS {
-                monkey = monkey.replace(label, "");
-            }
+fn process(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let lines: Vec<Line> = error::lines(input)
+        .filter(|line| !line.text.is_empty())
+        .collect();
 
-            let lines: Vec<&str> = monkey.lines().collect();
+    let monkeys: Vec<Monkey> = lines
+        .chunks(6)
+        .map(|monkey| {
+            let [_, items, op, test, if_true, if_false] = monkey else {
+                return Err(error::end_of_input(input, "the rest of the monkey's notes"));
+            };
 
-            Monkey {
-                items: get_items(lines[1]),
-                op: get_op(lines[2]),
-                test: lines[3].parse().unwrap(),
-                if_true: lines[4].parse().unwrap(),
-                if_false: lines[5].parse().unwrap(),
+            Ok(Monkey {
+                items: get_items(items)?,
+                op: get_op(op)?,
+                test: get_number(test, LABELS[2])?,
+                if_true: get_number(if_true, LABELS[3])?,
+                if_false: get_number(if_false, LABELS[4])?,
                 inspections: 0,
-            }
+            })
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    // the monkey business is the two busiest monkeys' inspections multiplied
+    if monkeys.len() < 2 {
+        return Err(error::end_of_input(input, "notes on at least two monkeys"));
+    }
+
+    for (monkey, lines) in monkeys.iter().zip(lines.chunks(6)) {
+        if monkey.test == 0 {
+            let test = line_value(&lines[3]);
+            return Err(lines[3].error(test, "a divisor above 0"));
+        }
+
+        for (target, line) in [(monkey.if_true, &lines[4]), (monkey.if_false, &lines[5])] {
+            if target >= monkeys.len() {
+                return Err(line.error(line_value(line), &format!("a monkey below {}", monkeys.len())));
+            }
+        }
+    }
+
+    Ok(monkeys)
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let mut monkeys = process(input);
+pub fn solve_part_one(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
 
     for _round in 0..ROUNDS {
         for m in 0..monkeys.len() {
@@ -112,13 +168,13 @@ pub fn solve_part_one(input: &str) -> usize {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspections.cmp(&a.inspections));
+    monkeys.sort_by_key(|monkey| std::cmp::Reverse(monkey.inspections));
 
-    monkeys.get(0).unwrap().inspections * monkeys.get(1).unwrap().inspections
+    monkeys.first().unwrap().inspections * monkeys.get(1).unwrap().inspections
 }
 
-pub fn solve(input: &str) -> usize {
-    let mut monkeys = process(input);
+pub fn solve(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
 
     let test_product = monkeys
         .iter()
@@ -148,7 +204,64 @@ pub fn solve(input: &str) -> usize {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspections.cmp(&a.inspections));
+    monkeys.sort_by_key(|monkey| std::cmp::Reverse(monkey.inspections));
+
+    monkeys.first().unwrap().inspections * monkeys.get(1).unwrap().inspections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day11::parse(EXAMPLE).unwrap()), 10605);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day11::parse(EXAMPLE).unwrap()), 2713310158);
+    }
+
+    #[test]
+    fn too_few_monkeys() {
+        assert!(Day11::parse("").is_err());
+        assert!(Day11::parse(&EXAMPLE[..EXAMPLE.find("\n\n").unwrap()]).is_err());
+    }
 
-    monkeys.get(0).unwrap().inspections * monkeys.get(1).unwrap().inspections
+    #[test]
+    fn zero_divisor() {
+        let err = Day11::parse(&EXAMPLE.replace("by 19", "by 0")).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (11, 22, "0"));
+    }
 }
@@ -1,17 +1,33 @@
-use std::collections::VecDeque;
+use crate::{error::{self, ParseError}, grid::{Coord, Grid}, point::Dir, search, solution::{Answer, Solution}};
 
 const START: char = 'S';
 const END: char = 'E';
 
-type Coord = (usize, usize);
+pub struct Day12;
 
-#[derive(Debug)]
-struct Square {
-    height: usize,
-    visited: bool,
-    parent: Option<Coord>,
-    is_start: bool,
-    is_end: bool,
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Grid<Square>;
+
+    fn parse(input: &str) -> Result<Grid<Square>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(squares: &Grid<Square>) -> Answer {
+        solve_part_one(squares).into()
+    }
+
+    fn part_two(squares: &Grid<Square>) -> Answer {
+        solve(squares).into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Square {
+    pub height: usize,
+    pub is_start: bool,
+    pub is_end: bool,
 }
 
 trait ToValue {
@@ -28,186 +44,165 @@ impl ToValue for char {
     }
 }
 
-trait Grid {
-    fn get_square(&self, square: Coord) -> &Square;
-    fn get_square_mut(&mut self, square: Coord) -> &mut Square;
-    fn find(&self, target: char) -> Coord;
-    fn get_adjacent(&self, square: Coord, is_reverse: bool) -> Vec<Coord>;
+/// The squares we can step to from `coord`, or could have stepped from when `is_reverse`.
+fn get_adjacent(
+    squares: &Grid<Square>,
+    coord: Coord,
+    is_reverse: bool,
+) -> impl Iterator<Item = Coord> + '_ {
+    let current_square = &squares[coord];
+
+    squares
+        .neighbours(coord)
+        .filter(move |adjacent| {
+            let adjacent_square = &squares[*adjacent];
+
+            if !is_reverse {
+                adjacent_square.height <= current_square.height + 1
+            } else {
+                current_square.height <= adjacent_square.height + 1
+            }
+        })
 }
 
-impl Grid for Vec<Vec<Square>> {
-    fn get_square(&self, coord: Coord) -> &Square {
-        self.get(coord.0).unwrap().get(coord.1).unwrap()
+fn process(input: &str) -> Result<Grid<Square>, ParseError> {
+    let squares = Grid::parse(input, "a height from a to z", |char| {
+        let height = match char {
+            START => 1,
+            END => 26,
+            'a'..='z' => char.to_value() as usize,
+            _ => return None,
+        };
+
+        Some(Square {
+            height,
+            is_start: char == START,
+            is_end: char == END,
+        })
+    })?;
+
+    if squares.position(|square| square.is_start).is_none() {
+        return Err(error::end_of_input(input, "a start square S"));
     }
 
-    fn get_square_mut(&mut self, coord: Coord) -> &mut Square {
-        self.get_mut(coord.0).unwrap().get_mut(coord.1).unwrap()
+    if squares.position(|square| square.is_end).is_none() {
+        return Err(error::end_of_input(input, "an end square E"));
     }
 
-    fn find(&self, target: char) -> Coord {
-        let (mut row_idx, mut col_idx) = (0, 0);
-
-        for (current_row, row) in self.iter().enumerate() {
-            for (current_col, square) in row.iter().enumerate() {
-                let is_start = target == START && square.is_start;
-                let is_end = target == END && square.is_end;
-
-                if is_start || is_end {
-                    (row_idx, col_idx) = (current_row, current_col);
-                }
-            }
-        }
-
-        (row_idx, col_idx)
-    }
-
-    fn get_adjacent(&self, coord: Coord, is_reverse: bool) -> Vec<Coord> {
-        let mut adjacent = vec!();
-        let current_square = self.get_square(coord);
-    
-        let coord = (coord.0 as isize, coord.1 as isize);
-    
-        let potentials: [(isize, isize); 4] = [
-            (coord.0, coord.1 - 1),
-            (coord.0, coord.1 + 1),
-            (coord.0 - 1, coord.1),
-            (coord.0 + 1, coord.1),
-        ];
-    
-        for potential in potentials {
-            let valid_row = potential.0 >= 0 && potential.0 < self.len() as isize;
-            let valid_col = potential.1 >= 0
-                && potential.1 < self.get(coord.0 as usize).unwrap().len() as isize;
-    
-            if valid_row && valid_col {
-                let adjacent_square = self.get_square((potential.0 as usize, potential.1 as usize));
-    
-                let is_unvisited = !adjacent_square.visited;
-
-                let is_valid_move = if !is_reverse {
-                    adjacent_square.height <= current_square.height + 1
-                } else {
-                    current_square.height <= adjacent_square.height + 1
-                };
-                
-    
-                if is_unvisited && is_valid_move {
-                    adjacent.push((potential.0 as usize, potential.1 as usize));
-                }
-            }
-        }
-    
-        adjacent
-    }
+    Ok(squares)
 }
 
-fn process(input: &str) -> Vec<Vec<Square>> {
-    input
-        .lines()
-        .map(|line| line
-            .chars()
-            .map(|char| {
-                let height = match char {
-                    START => 1,
-                    END => 26,
-                    _ => char.to_value() as usize,
-                };
-
-                Square {
-                    height,
-                    visited: false,
-                    parent: None,
-                    is_start: char == START,
-                    is_end: char == END,
-                }
-            })
-            .collect()
-        )
-        .collect()
+pub fn solve_part_one(squares: &Grid<Square>) -> Option<usize> {
+    get_route(squares).map(|route| route.len() - 1)
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let mut squares = process(input);
+pub fn solve(squares: &Grid<Square>) -> Option<usize> {
+    get_best_route(squares).map(|route| route.len() - 1)
+}
 
-    let start = squares.find(START);
-    let mut queue: VecDeque<Coord> = VecDeque::new();
+/// The fewest steps from the start to the end, as every square along the way, if the end can be
+/// reached at all.
+pub fn get_route(squares: &Grid<Square>) -> Option<Vec<Coord>> {
+    let start = squares.position(|square| square.is_start).unwrap();
+
+    search::bfs(
+        [start],
+        |coord| get_adjacent(squares, *coord, false),
+        |coord| squares[*coord].is_end,
+    )
+        .map(|path| path.nodes)
+}
 
-    squares.get_square_mut(start).visited = true;
-    queue.push_back(start);
+/// Like `get_route`, but from whichever square at the lowest height is closest to the end, which
+/// we find by searching back from the end.
+pub fn get_best_route(squares: &Grid<Square>) -> Option<Vec<Coord>> {
+    let end = squares.position(|square| square.is_end).unwrap();
 
-    while let Some(coord) = queue.pop_front() {
-        if squares.get_square(coord).is_end {
-            queue.clear();
-        } else {
-            for adjacent in squares.get_adjacent(coord, false).iter() {
-                let adjacent_square = squares.get_square_mut(*adjacent);
+    let mut route = search::bfs(
+        [end],
+        |coord| get_adjacent(squares, *coord, true),
+        |coord| squares[*coord].height == 1,
+    )?
+        .nodes;
 
-                adjacent_square.visited = true;
-                adjacent_square.parent = Some(coord);
+    route.reverse();
+    Some(route)
+}
 
-                queue.push_back(*adjacent);
-            }
-        }
+/// Draws `route` over the heightmap the way the puzzle does: each square on it has an arrow
+/// pointing to the next one, the end is `E` and everywhere else is `.`.
+pub fn render(squares: &Grid<Square>, route: &[Coord]) -> Grid<char> {
+    let mut map = squares.map(|_| '.');
+
+    for step in route.windows(2) {
+        let dir = Dir::ALL
+            .into_iter()
+            .find(|dir| squares.offset(step[0], dir.delta()) == Some(step[1]))
+            .expect("the route isn't a step at a time!");
+
+        map[step[0]] = match dir {
+            Dir::Up => '^',
+            Dir::Right => '>',
+            Dir::Down => 'v',
+            Dir::Left => '<',
+        };
     }
 
-    let mut steps = 0;
-    let mut found = false;
-    let mut current = squares.find(END);
-
-    while !found {
-        let current_square = squares.get_square(current);
-
-        if current_square.is_start {
-            found = true;
-        } else {
-            current = current_square.parent.unwrap();
-            steps += 1;
-        }
+    if let Some(end) = route.last() {
+        map[*end] = 'E';
     }
 
-    steps
+    map
 }
 
-pub fn solve(input: &str) -> usize {
-    let mut squares = process(input);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let start = squares.find(END);
-    let mut end_coord = (0, 0);
-    let mut queue: VecDeque<Coord> = VecDeque::new();
+    const EXAMPLE: &str = "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
 
-    squares.get_square_mut(start).visited = true;
-    queue.push_back(start);
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day12::parse(EXAMPLE).unwrap()), Some(31));
+    }
 
-    while let Some(coord) = queue.pop_front() {
-        if squares.get_square(coord).height == 1 {
-            end_coord = coord;
-            queue.clear();
-        } else {
-            for adjacent in squares.get_adjacent(coord, true).iter() {
-                let adjacent_square = squares.get_square_mut(*adjacent);
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day12::parse(EXAMPLE).unwrap()), Some(29));
+    }
 
-                adjacent_square.visited = true;
-                adjacent_square.parent = Some(coord);
+    #[test]
+    fn route() {
+        let squares = Day12::parse(EXAMPLE).unwrap();
+        let route = get_route(&squares).unwrap();
+        let map = render(&squares, &route).to_string();
 
-                queue.push_back(*adjacent);
-            }
-        }
+        assert_eq!(route.len(), 32);
+        assert!(squares[route[0]].is_start && squares[route[31]].is_end);
+        assert_eq!(map.chars().filter(|c| "^v<>".contains(*c)).count(), 31);
+        assert_eq!(map.lines().nth(2).unwrap().find('E'), Some(5));
     }
 
-    let mut steps = 0;
-    let mut found = false;
-    let mut current = end_coord;
+    #[test]
+    fn best_route() {
+        let squares = Day12::parse(EXAMPLE).unwrap();
+        let route = get_best_route(&squares).unwrap();
 
-    while !found {
-        let current_square = squares.get_square(current);
-
-        if current_square.is_end {
-            found = true;
-        } else {
-            current = current_square.parent.unwrap();
-            steps += 1;
-        }
+        assert_eq!(route.len(), 30);
+        assert_eq!(squares[route[0]].height, 1);
+        assert!(squares[route[29]].is_end);
     }
 
-    steps
+    #[test]
+    fn no_route() {
+        let squares = Day12::parse("SzE").unwrap();
+
+        assert_eq!(Day12::part_one(&squares), Answer::Unsolved);
+        assert_eq!(Day12::part_two(&squares), Answer::Unsolved);
+    }
 }
@@ -1,51 +1,72 @@
 use std::cmp::Ordering;
 
 use itertools::Itertools;
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<(Token, Token)>;
+
+    fn parse(input: &str) -> Result<Vec<(Token, Token)>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(pairs: &Vec<(Token, Token)>) -> Answer {
+        solve_part_one(pairs).into()
+    }
+
+    fn part_two(pairs: &Vec<(Token, Token)>) -> Answer {
+        solve(pairs).into()
+    }
+}
 
 #[derive(Clone, Debug, Eq)]
-enum Token {
+pub enum Token {
     List(Vec<Token>),
     Num(u8),
 }
 
 impl Token {
-    fn from_str(s: &str) -> (Token, usize) {
-        let chars: Vec<char> = s.chars().collect();
+    fn from_str(line: &Line, s: &str) -> Result<(Token, usize), ParseError> {
+        let bytes = s.as_bytes();
 
         let mut current_token = vec!();
-        let mut digits = String::new();
+        let mut digits_start = None;
         let mut i = 0;
 
-        loop {
-            match chars.get(i) {
-                Some(c) => {
-                    match c {
-                        '[' => {
-                            let (sub_token, consumed) = Token::from_str(&s[(i + 1)..]);
-                            current_token.push(sub_token);
-                            i += consumed + 1;
-                        },
-                        ',' => {
-                            if !digits.is_empty() {
-                                current_token.push(Token::Num(digits.parse().unwrap()));
-                                digits = String::new();
-                            }
-                        },
-                        ']' => break,
-                        c => digits.push(*c),
+        while let Some(c) = bytes.get(i) {
+            match c {
+                b'[' => {
+                    let (sub_token, consumed) = Token::from_str(line, &s[(i + 1)..])?;
+                    current_token.push(sub_token);
+                    i += consumed + 1;
+                },
+                b',' => {
+                    if let Some(start) = digits_start.take() {
+                        current_token.push(Token::Num(line.parse(&s[start..i])?));
                     }
                 },
-                None => break,
+                b']' => break,
+                b'0'..=b'9' => {
+                    digits_start.get_or_insert(i);
+                },
+                _ => {
+                    let len = s[i..].chars().next().map_or(1, char::len_utf8);
+                    return Err(line.error(&s[i..(i + len)], "a number, comma or bracket"));
+                },
             }
 
             i += 1;
         }
 
-        if !digits.is_empty() {
-            current_token.push(Token::Num(digits.parse().unwrap()));
+        if let Some(start) = digits_start {
+            current_token.push(Token::Num(line.parse(&s[start..i])?));
         }
 
-        (Token::List(current_token), i)
+        Ok((Token::List(current_token), i))
     }
 
     fn divider(num: u8) -> Token {
@@ -94,28 +115,20 @@ impl PartialEq for Token {
     }
 }
 
-fn process_pairs(input: &str) -> Vec<(Token, Token)> {
-    input
-        .split("\n\n")
-        .map(|pair| pair
-            .lines()
-            .map(|line| Token::from_str(line).0)
-            .collect_tuple()
-            .unwrap()
-        )
-        .collect()
-}
+fn process(input: &str) -> Result<Vec<(Token, Token)>, ParseError> {
+    let packets = error::lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| Ok(Token::from_str(&line, line.text)?.0))
+        .collect::<Result<Vec<Token>, ParseError>>()?;
+
+    if packets.len() % 2 != 0 {
+        return Err(error::end_of_input(input, "the second packet of the pair"));
+    }
 
-fn process_packets(input: &str) -> Vec<Token> {
-    input
-        .split_whitespace()
-        .map(|line| Token::from_str(line).0)
-        .collect()
+    Ok(packets.into_iter().tuples().collect())
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let pairs = process_pairs(input);
-    
+pub fn solve_part_one(pairs: &[(Token, Token)]) -> usize {
     pairs
         .iter()
         .enumerate()
@@ -127,8 +140,11 @@ pub fn solve_part_one(input: &str) -> usize {
         })
 }
 
-pub fn solve(input: &str) -> usize {
-    let mut packets = process_packets(input);
+pub fn solve(pairs: &[(Token, Token)]) -> usize {
+    let mut packets: Vec<Token> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
+        .collect();
 
     let mut dividers = vec!(Token::divider(2), Token::divider(6));
     packets.append(&mut dividers);
@@ -143,3 +159,43 @@ pub fn solve(input: &str) -> usize {
 
     div_idx_2 * div_idx_6
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day13::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day13::parse(EXAMPLE).unwrap()), 140);
+    }
+}
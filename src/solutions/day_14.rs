@@ -1,17 +1,54 @@
+use std::fmt;
 use itertools::Itertools;
+use crate::{error::{self, ParseError}, grid::{Coord, Grid}, point::{Compass, Dir, Point}, solution::{Answer, Solution}};
 
-#[derive(Clone, Debug, PartialEq)]
-struct Coord {
-    x: usize,
-    y: usize,
+const SOURCE_X: usize = 500;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Cave;
+
+    fn parse(input: &str) -> Result<Cave, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(cave: &Cave) -> Answer {
+        solve_part_one(cave).into()
+    }
+
+    fn part_two(cave: &Cave) -> Answer {
+        solve(cave).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+
+        write!(f, "{}", c)
+    }
 }
 
-#[derive(Debug)]
-struct Grid {
-    cells: Vec<Coord>,
-    x_lower: usize,
-    x_upper: usize,
-    y_upper: usize,
+/// The scanned rock, in a grid wide enough to hold the pile of sand on the floor. Column 0 is
+/// `x_offset` in the scan's coordinates, and the grid stops at the lowest rock.
+#[derive(Clone, Debug)]
+pub struct Cave {
+    pub grid: Grid<Tile>,
+    pub x_offset: usize,
 }
 
 enum Fall {
@@ -20,14 +57,14 @@ enum Fall {
     Out,
 }
 
-impl Grid {
-    fn new(paths: Vec<Vec<Coord>>, x_lower: usize, x_upper: usize, y_upper: usize) -> Grid {
-        let mut grid = Grid {
-            cells: vec!(),
-            x_lower,
-            x_upper,
-            y_upper,
-        };
+impl Cave {
+    fn new(paths: Vec<Vec<Point<usize>>>, x_lower: usize, x_upper: usize, y_upper: usize) -> Cave {
+        // Sand on the floor spreads at most as far sideways as it falls.
+        let spread = y_upper + 2;
+        let x_offset = x_lower.min(SOURCE_X.saturating_sub(spread)).saturating_sub(1);
+        let width = x_upper.max(SOURCE_X + spread) + 2 - x_offset;
+
+        let mut grid = Grid::new(width, y_upper + 1, Tile::Air);
 
         paths
             .iter()
@@ -49,96 +86,85 @@ impl Grid {
 
                     for x in x_range {
                         for y in y_range.clone() {
-                            grid.cells.push(Coord { x, y })
+                            grid[Point::new(x - x_offset, y)] = Tile::Rock;
                         }
                     }
                 })
             );
 
-        grid
+        Cave {
+            grid,
+            x_offset,
+        }
     }
 
-    fn is_cell(&self, coord: &Coord) -> bool {
-        self.cells.iter().find(|cell| *cell == coord).is_some()
+    fn source(&self) -> Coord {
+        Point::new(SOURCE_X - self.x_offset, 0)
     }
 
-    fn fall(&self, coord: &Coord) -> Fall {
-        let options = [
-            Coord { x: coord.x, y: coord.y + 1 },
-            Coord { x: coord.x - 1, y: coord.y + 1 },
-            Coord { x: coord.x + 1, y: coord.y + 1 }
-        ];
-
-        for option in options {
-            if self.is_out(&option) {
-                return Fall::Out;
-            } else if !self.is_cell(&option) {
-                return Fall::Move(option);
+    fn fall(&self, coord: Coord) -> Fall {
+        for delta in [Dir::Down.delta(), Compass::SW.delta(), Compass::SE.delta()] {
+            match self.grid.offset(coord, delta) {
+                None => return Fall::Out,
+                Some(option) if self.grid[option] == Tile::Air => return Fall::Move(option),
+                Some(_) => (),
             }
         }
 
         Fall::Stay
     }
 
-    fn is_out(&self, coord: &Coord) -> bool {
-        coord.x < self.x_lower || coord.x > self.x_upper || coord.y > self.y_upper
-    }
-
     fn add_floor(&mut self) {
-        self.x_lower = 0;
-        self.x_upper = 1000;
-        self.y_upper += 2;
+        let width = self.grid.width();
 
-        for x in self.x_lower..=self.x_upper {
-            self.cells.push(Coord { x, y: self.y_upper });
-        }
+        self.grid.push_row(vec![Tile::Air; width]);
+        self.grid.push_row(vec![Tile::Rock; width]);
     }
 }
 
-fn process(input: &str) -> Grid {
+fn process(input: &str) -> Result<Cave, ParseError> {
     let mut x_lower = usize::MAX;
     let mut x_upper = 0;
     let mut y_upper = 0;
 
-    let paths: Vec<Vec<Coord>> = input
-        .lines()
-        .map(|line| line
+    let paths: Vec<Vec<Point<usize>>> = error::lines(input)
+        .map(|line| line.text
             .split(" -> ")
             .map(|coord| {
-                let (x, y) = coord
-                    .split(",")
-                    .map(|i| i.parse().unwrap())
-                    .collect_tuple().unwrap();
+                let (x, y) = line.split_once(coord, ",")?;
+                let (x, y) = (line.parse(x)?, line.parse(y)?);
 
                 if x < x_lower { x_lower = x }
                 if x > x_upper { x_upper = x }
                 if y > y_upper { y_upper = y }
 
-                Coord { x, y }
+                Ok(Point::new(x, y))
             })
             .collect()
         )
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    let grid = Grid::new(paths, x_lower, x_upper, y_upper);
+    if paths.is_empty() {
+        return Err(error::end_of_input(input, "a rock path"));
+    }
 
-    grid
+    Ok(Cave::new(paths, x_lower, x_upper, y_upper))
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let mut grid = process(input);
+pub fn solve_part_one(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
 
     let mut sand_count = 0;
     let mut done = false;
 
     while !done {
-        let mut sand_coord = Coord { x: 500, y: 0 };
+        let mut sand_coord = cave.source();
 
         loop {
-            match grid.fall(&sand_coord) {
+            match cave.fall(sand_coord) {
                 Fall::Move(coord) => sand_coord = coord,
                 Fall::Stay => {
-                    grid.cells.push(sand_coord);
+                    cave.grid[sand_coord] = Tile::Sand;
                     sand_count += 1;
                     break;
                 },
@@ -153,23 +179,23 @@ pub fn solve_part_one(input: &str) -> usize {
     sand_count
 }
 
-pub fn solve(input: &str) -> usize {
-    let mut grid = process(input);
+pub fn solve(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
 
-    grid.add_floor();
+    cave.add_floor();
 
     let mut sand_count = 0;
     let mut done = false;
-    let origin = Coord { x: 500, y: 0 };
+    let origin = cave.source();
 
     while !done {
-        let mut sand_coord = origin.clone();
+        let mut sand_coord = origin;
 
         loop {
-            match grid.fall(&sand_coord) {
+            match cave.fall(sand_coord) {
                 Fall::Move(coord) => sand_coord = coord,
                 Fall::Stay => {
-                    grid.cells.push(sand_coord.clone());
+                    cave.grid[sand_coord] = Tile::Sand;
                     sand_count += 1;
 
                     if sand_coord == origin {
@@ -184,3 +210,22 @@ pub fn solve(input: &str) -> usize {
 
     sand_count
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day14::parse(EXAMPLE).unwrap()), 24);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day14::parse(EXAMPLE).unwrap()), 93);
+    }
+}
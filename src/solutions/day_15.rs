@@ -1,34 +1,55 @@
 use itertools::Itertools;
 use std::cmp::max;
+use crate::{error::{self, ParseError}, point::Point, solution::{Answer, Solution}};
 
-const TARGET_TEST_Y: isize = 10;
 const TARGET_Y: isize = 2000000;
 
 const LIMIT_Y: isize = 4000000;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Coord {
-    x: isize,
-    y: isize,
+const TUNING_MULTIPLIER: isize = 4000000;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Vec<Sensor>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(sensors: &Vec<Sensor>) -> Answer {
+        solve_part_one(sensors).into()
+    }
+
+    fn part_two(sensors: &Vec<Sensor>) -> Answer {
+        solve(sensors).into()
+    }
 }
 
 #[derive(Debug)]
-struct Sensor {
-    pos: Coord,
-    beacon: Coord,
+pub struct Sensor {
+    pub pos: Point,
+    pub beacon: Point,
 }
 
 impl Sensor {
-    fn distance(&self) -> isize {
-        let d_x = self.pos.x - self.beacon.x;
-        let d_y = self.pos.y - self.beacon.y;
-        d_x.abs() + d_y.abs()
+    pub fn distance(&self) -> isize {
+        self.pos.manhattan(self.beacon)
     }
 }
 
-pub fn solve_part_one(input: &str) -> isize {
-    let sensors = process(input);
-    let ranges = get_ranges(&sensors, TARGET_Y);
+pub fn solve_part_one(sensors: &[Sensor]) -> isize {
+    count_covered(sensors, TARGET_Y)
+}
+
+pub fn count_covered(sensors: &[Sensor], target_y: isize) -> isize {
+    let ranges = get_ranges(sensors, target_y);
+
+    if ranges.is_empty() {
+        return 0;
+    }
 
     let mut covered = 0;
     let mut current_start = ranges.first().unwrap().0;
@@ -42,18 +63,21 @@ pub fn solve_part_one(input: &str) -> isize {
     covered -= sensors.iter()
         .map(|sensor| &sensor.beacon)
         .unique()
-        .filter(|beacon| beacon.y == TARGET_Y)
+        .filter(|beacon| beacon.y == target_y)
         .count() as isize;
 
     covered
 }
 
-pub fn solve(input: &str) -> isize {
-    let sensors = process(input);
+pub fn solve(sensors: &[Sensor]) -> Option<isize> {
+    get_tuning_frequency(sensors, LIMIT_Y)
+}
+
+pub fn get_tuning_frequency(sensors: &[Sensor], limit_y: isize) -> Option<isize> {
     let mut distress_beacon = None;
 
-    for y in 0..=LIMIT_Y {
-        let ranges = get_ranges(&sensors, y);
+    for y in 0..=limit_y {
+        let ranges = get_ranges(sensors, y);
     
         if ranges.is_empty() {
             continue;
@@ -76,34 +100,32 @@ pub fn solve(input: &str) -> isize {
         }
     }
 
-    let distress_beacon = distress_beacon.expect("no distress beacon found!");
-
-    distress_beacon.0 * LIMIT_Y + distress_beacon.1
+    distress_beacon.map(|(x, y)| x * TUNING_MULTIPLIER + y)
 }
 
-fn process(input: &str) -> Vec<Sensor> {
-    input
-        .lines()
+fn process(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    let sensors: Vec<Sensor> = error::lines(input)
         .map(|line| {
-            let line = line
-                .replace("Sensor at x=", "")
-                .replace(", y=", ",")
-                .replace(": closest beacon is at x=", ",");
-
-            let (s_x, s_y, b_x, b_y) = line
-                .split(",")
-                .map(|num| num.parse().unwrap())
-                .collect_tuple().unwrap();
-
-            Sensor {
-                pos: Coord { x: s_x, y: s_y },
-                beacon: Coord { x: b_x, y: b_y },
-            }
+            let rest = line.strip_prefix(line.text, "Sensor at x=")?;
+            let (s_x, rest) = line.split_once(rest, ", y=")?;
+            let (s_y, rest) = line.split_once(rest, ": closest beacon is at x=")?;
+            let (b_x, b_y) = line.split_once(rest, ", y=")?;
+
+            Ok(Sensor {
+                pos: Point::new(line.parse(s_x)?, line.parse(s_y)?),
+                beacon: Point::new(line.parse(b_x)?, line.parse(b_y)?),
+            })
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    if sensors.is_empty() {
+        return Err(error::end_of_input(input, "a sensor"));
+    }
+
+    Ok(sensors)
 }
 
-fn get_ranges(sensors: &Vec<Sensor>, target: isize) -> Vec<(isize, isize)> {
+pub fn get_ranges(sensors: &[Sensor], target: isize) -> Vec<(isize, isize)> {
     let mut ranges: Vec<(isize, isize)> = sensors
         .iter()
         .filter_map(|sensor| {
@@ -121,7 +143,61 @@ fn get_ranges(sensors: &Vec<Sensor>, target: isize) -> Vec<(isize, isize)> {
         })
         .collect();
 
-    ranges.sort_by(|a, b| a.0.cmp(&b.0));
+    ranges.sort_by_key(|range| range.0);
 
     ranges
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET_TEST_Y: isize = 10;
+    const LIMIT_TEST_Y: isize = 20;
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(count_covered(&Day15::parse(EXAMPLE).unwrap(), TARGET_TEST_Y), 26);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(get_tuning_frequency(&Day15::parse(EXAMPLE).unwrap(), LIMIT_TEST_Y), Some(56000011));
+    }
+
+    #[test]
+    fn bad_coordinate() {
+        let err = Day15::parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=l5").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (15, 1, 51));
+        assert_eq!(err.text, "l5");
+    }
+
+    #[test]
+    fn no_sensors() {
+        assert_eq!(Day15::parse("").unwrap_err().expected, "a sensor");
+    }
+
+    #[test]
+    fn no_distress_beacon() {
+        let sensors = Day15::parse("Sensor at x=0, y=0: closest beacon is at x=1, y=0").unwrap();
+
+        assert_eq!(get_tuning_frequency(&sensors, LIMIT_TEST_Y), None);
+    }
+}
@@ -1,53 +1,89 @@
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::{cmp::max, collections::HashMap};
+use crate::{error::{self, ParseError}, search, solution::{Answer, Solution}};
 
 const MINUTES: usize = 30;
+const TEACHING_MINUTES: usize = 4;
 const START: &str = "AA";
 
-struct Graph {
-    nodes: HashMap<String, usize>,
-    edges: HashMap<String, Vec<String>>,
-}
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        Graph::new(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(graph: &Graph) -> Answer {
+        solve_part_one(graph).into()
+    }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-enum Action {
-    Move(String),
-    Activate(String),
+    fn part_two(graph: &Graph) -> Answer {
+        solve(graph).into()
+    }
 }
 
-type MemoKey = (String, String, String, usize);
-type Memo = HashMap<MemoKey, (Vec<Action>, usize)>;
+#[derive(Debug)]
+pub struct Graph {
+    pub nodes: HashMap<String, usize>,
+    pub edges: HashMap<String, Vec<String>>,
+}
 
 impl Graph {
-    fn new(input: &str) -> Graph {
+    fn new(input: &str) -> Result<Graph, ParseError> {
         let mut graph = Graph {
             nodes: HashMap::new(),
             edges: HashMap::new(),
         };
 
-        input
-            .lines()
-            .for_each(|line| {
-                let line = line
-                    .replace("Valve ", "")
-                    .replace(" has flow rate=", ";")
-                    .replace("; tunnel leads to ", ";")
-                    .replace("; tunnels lead to ", ";")
-                    .replace("valve ", "")
-                    .replace("valves ", "");
-
-                let (key, rate, adj) = line
-                    .split(";")
-                    .collect_tuple().unwrap();
-
-                graph.nodes.insert(key.to_string(), rate.parse().unwrap());
-
-                adj.split(", ").for_each(|adj_key|
-                    graph.add_edge(key.to_string(), adj_key.to_string())
-                );
+        let mut tunnels = vec!();
+        let mut flowing = vec!();
+
+        for line in error::lines(input) {
+            let rest = line.strip_prefix(line.text, "Valve ")?;
+            let (key, rest) = line.split_once(rest, " has flow rate=")?;
+            let (rate_text, adj) = line.split_once(rest, "; ")?;
+
+            let adj = adj
+                .strip_prefix("tunnels lead to valves ")
+                .or_else(|| adj.strip_prefix("tunnel leads to valve "))
+                .ok_or_else(|| line.error(adj, "the valves the tunnels lead to"))?;
+
+            let rate = line.parse(rate_text)?;
+
+            if rate > 0 {
+                flowing.push((line, rate_text));
+            }
+
+            graph.nodes.insert(key.to_string(), rate);
+
+            adj.split(", ").for_each(|adj_key| {
+                graph.add_edge(key.to_string(), adj_key.to_string());
+                tunnels.push((line, adj_key));
             });
+        }
+
+        for (line, adj_key) in tunnels {
+            if !graph.nodes.contains_key(adj_key) {
+                return Err(line.error(adj_key, "a known valve"));
+            }
+        }
+
+        if !graph.nodes.contains_key(START) {
+            return Err(error::end_of_input(input, &format!("valve {}", START)));
+        }
+
+        // each valve worth visiting gets a bit of a u64, including the start
+        let limit = if graph.nodes[START] > 0 { 64 } else { 63 };
+
+        if let Some((line, rate)) = flowing.get(limit) {
+            return Err(line.error(rate, &format!("at most {} valves with flow", limit)));
+        }
 
-        graph
+        Ok(graph)
     }
 
     fn add_edge(&mut self, key_a: String, key_b: String) {
@@ -61,174 +97,145 @@ impl Graph {
     }
 }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let graph = Graph::new(input);
-
-    let current_node = START.to_string();
-    let activated: HashSet<String> = HashSet::new();
-    let mut memo: Memo = HashMap::new();
+pub fn solve_part_one(graph: &Graph) -> usize {
+    get_pressures(graph, MINUTES)
+        .into_values()
+        .max()
+        .unwrap()
+}
 
-    let moves = get_best_path(&graph, current_node, activated, MINUTES, vec!(), 0, &mut memo);
+/// We each open a different set of valves, so the best we can do together is the best pair of
+/// sets that don't overlap.
+pub fn solve(graph: &Graph) -> usize {
+    let pressures: Vec<(u64, usize)> = get_pressures(graph, MINUTES - TEACHING_MINUTES)
+        .into_iter()
+        .collect();
 
-    moves.1
+    pressures
+        .iter()
+        .flat_map(|(opened_a, pressure_a)| pressures
+            .iter()
+            .filter(move |(opened_b, _)| opened_a & opened_b == 0)
+            .map(move |(_, pressure_b)| pressure_a + pressure_b)
+        )
+        .max()
+        .unwrap()
 }
 
-pub fn solve(input: &str) -> usize {
-    let graph = Graph::new(input);
+/// The most pressure that can be released within `minutes` by opening each set of valves that
+/// can be opened in time, keyed by a bit per valve.
+///
+/// Only the start and valves with some flow are worth stopping at, so we walk straight between
+/// those using the fewest minutes it takes to get from each to the others.
+fn get_pressures(graph: &Graph, minutes: usize) -> HashMap<u64, usize> {
+    let valves: Vec<&String> = graph.nodes
+        .iter()
+        .filter(|(key, rate)| **rate > 0 || *key == START)
+        .map(|(key, _)| key)
+        .sorted()
+        .collect();
 
-    let current_node = START.to_string();
-    let activated: HashSet<String> = HashSet::new();
-    let mut memo: Memo = HashMap::new();
+    // valves in a separate part of the cave from each other have no distance
+    let distances: Vec<Vec<Option<usize>>> = valves
+        .iter()
+        .map(|from| {
+            let distances = search::distances([*from], |key| graph.edges[*key].iter());
 
-    let moves_a = get_best_path(
-        &graph,
-        current_node.clone(),
-        activated,
-        MINUTES - 4,
-        vec!(),
-        0,
-        &mut memo,
-    );
+            valves.iter().map(|to| distances.get(to).copied()).collect()
+        })
+        .collect();
 
-    let mut activated: HashSet<String> = HashSet::new();
+    let rates: Vec<usize> = valves.iter().map(|key| graph.nodes[*key]).collect();
+    let start = valves.iter().position(|key| *key == START).unwrap();
 
-    moves_a.0
-        .iter()
-        .for_each(|m| match m {
-            Action::Activate(node) => {
-                activated.insert(node.to_string());
-            },
-            Action::Move(_) => (),
-        });
-
-    let mut memo: Memo = HashMap::new();
-
-    let moves_b = get_best_path(
-        &graph,
-        current_node,
-        activated,
-        MINUTES - 4,
-        vec!(),
-        0,
-        &mut memo,
-    );
-
-    moves_a.1 + moves_b.1
+    let mut pressures = HashMap::new();
+
+    visit(&rates, &distances, start, minutes, 0, 0, &mut pressures);
+
+    pressures
 }
 
-fn get_best_path(
-    graph: &Graph,
-    current_node: String,
-    activated: HashSet<String>,
+fn visit(
+    rates: &[usize],
+    distances: &[Vec<Option<usize>>],
+    current: usize,
     minutes: usize,
-    path: Vec<Action>,
-    score: usize,
-    memo: &mut Memo,
-) -> (Vec<Action>, usize) {
-    if minutes == 0 {
-        return (path, score);
-    }
-
-    let mut options: Vec<(Vec<Action>, usize)> = vec!();
-
-    let is_inactive = !activated.contains(&current_node);
-    let rate_is_nonzero = *graph.nodes.get(&current_node).unwrap() > 0;
-
-    if is_inactive && rate_is_nonzero {
-        let option = Action::Activate(current_node.clone());
-
-        let mut option_activated = activated.clone();
-        option_activated.insert(current_node.clone());
-
-        let mut option_path = path.clone();
-        option_path.push(option.clone());
-
-        let memo_key = get_memo_key(
-            current_node.clone(),
-            current_node.clone(),
-            &option_activated,
-            minutes,
-        );
-
-        let mut option_best_path = match memo.get(&memo_key) {
-            Some(result) => result.clone(),
-            None => {
-                let result = get_best_path(
-                    graph,
-                    current_node.clone(),
-                    option_activated,
-                    minutes - 1,
-                    option_path,
-                    score,
-                    memo,
-                );
-                memo.insert(memo_key, result.clone());
-                result
-            },
+    opened: u64,
+    pressure: usize,
+    pressures: &mut HashMap<u64, usize>,
+) {
+    let best = pressures.entry(opened).or_insert(0);
+    *best = max(*best, pressure);
+
+    for next in 0..rates.len() {
+        let Some(distance) = distances[current][next] else {
+            continue;
         };
 
-        let activation_score = (minutes - 1) * graph.nodes.get(&current_node).unwrap();
-        option_best_path.1 += activation_score;
-
-        options.push(option_best_path);
-    }
-
-    if minutes > 1 {
-        for neighbour in graph.edges.get(&current_node).unwrap() {
-            let option = Action::Move(neighbour.clone());
+        let is_closed = opened & (1 << next) == 0;
+        let cost = distance + 1;
 
-            let mut option_path = path.clone();
-            option_path.push(option.clone());
+        if is_closed && rates[next] > 0 && cost < minutes {
+            let minutes = minutes - cost;
 
-            let memo_key = get_memo_key(
-                current_node.clone(),
-                neighbour.clone(),
-                &activated,
+            visit(
+                rates,
+                distances,
+                next,
                 minutes,
+                opened | (1 << next),
+                pressure + minutes * rates[next],
+                pressures,
             );
-
-            let option_best_path = match memo.get(&memo_key) {
-                Some(result) => result.clone(),
-                None => {
-                    let result = get_best_path(
-                        graph,
-                        neighbour.clone(),
-                        activated.clone(),
-                        minutes - 1,
-                        option_path,
-                        score,
-                        memo,
-                    );
-                    memo.insert(memo_key, result.clone());
-                    result
-                },
-            };
-
-            options.push(option_best_path);
         }
     }
+}
 
-    if options.is_empty() {
-        return (path, score);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day16::parse(EXAMPLE).unwrap()), 1651);
     }
 
-    let best_option = options
-        .iter()
-        .max_by(|a, b| a.1.cmp(&b.1))
-        .unwrap();
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day16::parse(EXAMPLE).unwrap()), 1707);
+    }
 
-    (best_option.0.clone(), score + best_option.1)
-}
+    #[test]
+    fn unreachable_valve() {
+        let graph = Day16::parse("\
+Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5; tunnel leads to valve AA
+Valve CC has flow rate=7; tunnel leads to valve DD
+Valve DD has flow rate=0; tunnel leads to valve CC").unwrap();
 
-fn get_memo_key(
-    current_node: String,
-    target_node: String,
-    activated: &HashSet<String>,
-    minutes: usize,
-) -> MemoKey {
-    let mut activated: Vec<String> = activated.iter().cloned().collect();
-    activated.sort();
-    let activated = activated.iter().join("");
+        assert_eq!(solve_part_one(&graph), 28 * 5);
+    }
 
-    (current_node, target_node, activated, minutes)
+    #[test]
+    fn too_many_valves() {
+        let input = (0..64)
+            .map(|n| format!("Valve {:02} has flow rate=1; tunnel leads to valve AA", n))
+            .chain(["Valve AA has flow rate=0; tunnel leads to valve 00".to_string()])
+            .join("\n");
+        let err = Day16::parse(&input).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (64, 24, "1"));
+    }
 }
@@ -1,41 +1,28 @@
-use std::{ops::Add, cmp::max, collections::{HashSet, HashMap}};
+use std::{cmp::max, collections::{HashSet, HashMap}};
+use crate::{error::{self, ParseError}, grid::Grid, point::Point, solution::{Answer, Solution}};
+
+const WIDTH: usize = 7;
 
 const COUNT_ONE: usize = 2022;
 const COUNT: usize = 1000000000000;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-struct Cell {
-    x: isize,
-    y: isize,
-}
+pub struct Day17;
 
-impl Add for Cell {
-    type Output = Cell;
+impl Solution for Day17 {
+    const DAY: u8 = 17;
 
-    fn add(self, other: Cell) -> Cell {
-        Cell {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    type Input = Vec<Dir>;
+
+    fn parse(input: &str) -> Result<Vec<Dir>, ParseError> {
+        parse(input).map_err(|err| err.on_day(Self::DAY))
     }
-}
 
-impl Cell {
-    fn moved(&self, dir: &Dir) -> Cell {
-        match dir {
-            Dir::Left => Cell {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Dir::Right => Cell {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Dir::Down => Cell {
-                x: self.x,
-                y: self.y - 1,
-            },
-        }
+    fn part_one(jets: &Vec<Dir>) -> Answer {
+        solve_part_one(jets).into()
+    }
+
+    fn part_two(jets: &Vec<Dir>) -> Answer {
+        solve(jets).into()
     }
 }
 
@@ -50,43 +37,43 @@ enum RockType {
 
 struct Rock {
     rock_type: RockType,
-    pos: Cell,
+    pos: Point,
 }
 
 impl Rock {
-    fn cells(&self) -> Vec<Cell> {
+    fn cells(&self) -> Vec<Point> {
         match self.rock_type {
             RockType::A => vec!(
-                Cell { x: 0, y: 0 },
-                Cell { x: 1, y: 0 },
-                Cell { x: 2, y: 0 },
-                Cell { x: 3, y: 0 },
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
             ),
             RockType::B => vec!(
-                Cell { x: 0, y: 1 },
-                Cell { x: 1, y: 0 },
-                Cell { x: 1, y: 1 },
-                Cell { x: 1, y: 2 },
-                Cell { x: 2, y: 1 },
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(1, 1),
+                Point::new(1, 2),
+                Point::new(2, 1),
             ),
             RockType::C => vec!(
-                Cell { x: 0, y: 0 },
-                Cell { x: 1, y: 0 },
-                Cell { x: 2, y: 0 },
-                Cell { x: 2, y: 1 },
-                Cell { x: 2, y: 2 },
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(2, 1),
+                Point::new(2, 2),
             ),
             RockType::D => vec!(
-                Cell { x: 0, y: 0 },
-                Cell { x: 0, y: 1 },
-                Cell { x: 0, y: 2 },
-                Cell { x: 0, y: 3 },
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(0, 3),
             ),
             RockType::E => vec!(
-                Cell { x: 0, y: 0 },
-                Cell { x: 0, y: 1 },
-                Cell { x: 1, y: 0 },
-                Cell { x: 1, y: 1 },
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(1, 1),
             ),
         }
     }
@@ -112,55 +99,52 @@ impl Rock {
     }
 }
 
-enum Dir {
+pub enum Dir {
     Left,
     Right,
     Down,
 }
 
-type Permutation = (String, usize, usize);
-
-trait CellSet {
-    fn to_string(&self, height: isize) -> String;
+impl Dir {
+    /// The tower grows upwards, so unlike `point::Dir` down is `y - 1`.
+    fn delta(&self) -> Point {
+        match self {
+            Dir::Left => Point::new(-1, 0),
+            Dir::Right => Point::new(1, 0),
+            Dir::Down => Point::new(0, -1),
+        }
+    }
 }
 
-impl CellSet for HashSet<Cell> {
-    fn to_string(&self, height: isize) -> String {
-        let mut strings = self
-            .iter()
-            .map(|cell| format!("{},{}", cell.x, cell.y - height))
-            .collect::<Vec<String>>();
+type Permutation = (String, usize, usize);
 
-        strings.sort();
+pub fn solve_part_one(jets: &[Dir]) -> isize {
+    get_height(jets, COUNT_ONE)
+}
 
-        strings.join(",")
-    }
+pub fn solve(jets: &[Dir]) -> isize {
+    get_height(jets, COUNT)
 }
 
-pub fn solve(input: &str) -> isize {
+fn get_height(jets: &[Dir], target_count: usize) -> isize {
     let mut height = 0;
-    let mut occupied = HashSet::<Cell>::new();
+    // Row 0 is the first row above the floor.
+    let mut occupied = Grid::new(WIDTH, 0, false);
 
     let rock_types = [RockType::A, RockType::B, RockType::C, RockType::D, RockType::E];
     let mut rock_type = 0;
 
-    let jets = parse(input);
     let mut jet = 0;
 
-    let target_count = COUNT;
-
     let mut permutations = HashMap::<Permutation, (usize, isize)>::new();
     let mut cycle_found = false;
-    let mut cycle_start_height = 0;
-    let mut cycle_height = 0;
-    let mut cycle_start = 0;
-    let mut cycle_len = 0;
+    let mut skipped_height = 0;
     let mut remaining_count = 0;
 
     for count in 0..target_count {
         let mut rock = Rock {
             rock_type: rock_types[rock_type],
-            pos: Cell { x: 2, y: height + 4 },
+            pos: Point::new(2, height + 4),
         };
 
         rock_type += 1;
@@ -169,21 +153,21 @@ pub fn solve(input: &str) -> isize {
         }
 
         loop {
-            let new_pos = rock.pos.moved(jets.get(jet).unwrap());
+            let new_pos = rock.pos + jets[jet].delta();
 
             jet += 1;
             if jet == jets.len() {
                 jet = 0;
             }
 
-            let within_walls = new_pos.x >= 0 && new_pos.x + rock.width() <= 7;
+            let within_walls = new_pos.x >= 0 && new_pos.x + rock.width() <= WIDTH as isize;
             let can_move = within_walls && !is_collision(&occupied, &new_pos, &rock);
 
             if can_move {
                 rock.pos = new_pos;
             }
 
-            let new_pos = rock.pos.moved(&Dir::Down);
+            let new_pos = rock.pos + Dir::Down.delta();
 
             let is_floor = new_pos.y == 0;
             let can_move = !is_floor && !is_collision(&occupied, &new_pos, &rock);
@@ -193,21 +177,19 @@ pub fn solve(input: &str) -> isize {
             } else {
                 settle(&mut occupied, &rock);
                 height = max(height, rock.pos.y + rock.height() - 1);
-                occupied = remove_out_of_reach(occupied, height);
-                
+
                 if !cycle_found {
-                    let permutation = (occupied.to_string(height), rock_type, jet);
+                    let permutation = (get_surface(&occupied, height), rock_type, jet);
 
                     if let Some(prev_permutation) = permutations.get(&permutation) {
                         cycle_found = true;
-    
-                        cycle_start = prev_permutation.0;
-                        cycle_len = count - prev_permutation.0;
-    
-                        cycle_start_height = prev_permutation.1;
-                        cycle_height = height - prev_permutation.1;
-    
-                        remaining_count = (target_count - cycle_start) % cycle_len;
+
+                        let cycle_len = count - prev_permutation.0;
+                        let cycle_height = height - prev_permutation.1;
+                        let rocks_left = target_count - count - 1;
+
+                        skipped_height = (rocks_left / cycle_len) as isize * cycle_height;
+                        remaining_count = rocks_left % cycle_len;
                     } else {
                         permutations.insert(permutation, (count, height));
                     }
@@ -218,91 +200,119 @@ pub fn solve(input: &str) -> isize {
         }
 
         if cycle_found {
-            remaining_count -= 1;
             if remaining_count == 0 {
                 break;
             }
+            remaining_count -= 1;
         }
     }
 
-    if cycle_found {
-        let n_cycles = ((target_count - cycle_start) / cycle_len) as isize;
-        let remaining_height = height - cycle_start_height - cycle_height;
+    height + skipped_height
+}
 
-        cycle_start_height + n_cycles * cycle_height + remaining_height
-    } else {
-        height
+fn parse(input: &str) -> Result<Vec<Dir>, ParseError> {
+    let jets: Vec<Dir> = error::lines(input.trim())
+        .flat_map(|line| line.text
+            .char_indices()
+            .map(move |(idx, c)| match c {
+                '<' => Ok(Dir::Left),
+                '>' => Ok(Dir::Right),
+                _ => Err(line.error(&line.text[idx..idx + c.len_utf8()], "< or >")),
+            })
+        )
+        .collect::<Result<_, _>>()?;
+
+    if jets.is_empty() {
+        return Err(error::end_of_input(input, "a jet pattern"));
     }
+
+    Ok(jets)
 }
 
-fn parse(input: &str) -> Vec<Dir> {
-    input
-        .trim()
-        .chars()
-        .map(|c| match c {
-            '<' => Dir::Left,
-            '>' => Dir::Right,
-            _ => panic!("bad input"),
-        })
-        .collect()
+fn is_occupied(occupied: &Grid<bool>, cell: &Point) -> bool {
+    cell.y > 0 && occupied
+        .get(Point::new(cell.x as usize, cell.y as usize - 1))
+        .copied()
+        .unwrap_or(false)
 }
 
-fn is_collision(occupied: &HashSet<Cell>, pos: &Cell, rock: &Rock) -> bool {
+fn is_collision(occupied: &Grid<bool>, pos: &Point, rock: &Rock) -> bool {
     rock
         .cells()
         .iter()
-        .any(|cell| {
-            let cell = *cell + *pos;
-            occupied.contains(&cell)
-        })
+        .any(|cell| is_occupied(occupied, &(*cell + *pos)))
 }
 
-fn settle(occupied: &mut HashSet<Cell>, rock: &Rock) {
+fn settle(occupied: &mut Grid<bool>, rock: &Rock) {
     rock
         .cells()
         .iter()
         .for_each(|cell| {
             let cell = *cell + rock.pos;
-            occupied.insert(cell);
+
+            while occupied.height() < cell.y as usize {
+                occupied.push_row(vec![false; WIDTH]);
+            }
+
+            occupied[Point::new(cell.x as usize, cell.y as usize - 1)] = true;
         });
 }
 
-fn remove_out_of_reach(occupied: HashSet<Cell>, height: isize) -> HashSet<Cell> {
-    let mut empty = HashSet::<Cell>::new();
-    let mut occupied_edge = HashSet::<Cell>::new();
-    
-    empty.insert(Cell { x: 0, y: height + 1 });
-    let mut explored_count = 1;
-    let mut done = false;
+/// Describes the occupied cells that a falling rock could still reach, relative to `height`,
+/// found by flooding the empty space down from just above the tower.
+fn get_surface(occupied: &Grid<bool>, height: isize) -> String {
+    let mut empty = HashSet::<Point>::new();
+    let mut occupied_edge = HashSet::<Point>::new();
+    let mut queue = vec!(Point::new(0, height + 1));
 
-    while !done {
-        for cell in empty.clone().iter() {
-            let mut neighbours = Vec::<Cell>::new();
+    empty.insert(queue[0]);
 
-            if cell.x > 0 {
-                neighbours.push(cell.moved(&Dir::Left));
-            }
-            if cell.x < 6 {
-                neighbours.push(cell.moved(&Dir::Right));
-            }
-            if cell.y > 1 {
-                neighbours.push(cell.moved(&Dir::Down));
-            }
+    while let Some(cell) = queue.pop() {
+        let mut neighbours = Vec::<Point>::new();
 
-            for neighbour in neighbours.iter() {
-                if occupied.contains(neighbour) {
-                    occupied_edge.insert(*neighbour);
-                } else {
-                    empty.insert(*neighbour);
-                }
-            }
+        if cell.x > 0 {
+            neighbours.push(cell + Dir::Left.delta());
+        }
+        if cell.x < WIDTH as isize - 1 {
+            neighbours.push(cell + Dir::Right.delta());
+        }
+        if cell.y > 1 {
+            neighbours.push(cell + Dir::Down.delta());
         }
 
-        if explored_count == empty.len() + occupied_edge.len() {
-            done = true;
+        for neighbour in neighbours {
+            if is_occupied(occupied, &neighbour) {
+                occupied_edge.insert(neighbour);
+            } else if empty.insert(neighbour) {
+                queue.push(neighbour);
+            }
         }
-        explored_count = empty.len() + occupied_edge.len();
     }
 
-    occupied_edge
+    let mut strings = occupied_edge
+        .iter()
+        .map(|cell| (*cell - Point::new(0, height)).to_string())
+        .collect::<Vec<String>>();
+
+    strings.sort();
+
+    strings.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+>>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day17::parse(EXAMPLE).unwrap()), 3068);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day17::parse(EXAMPLE).unwrap()), 1514285714288);
+    }
 }
@@ -1,64 +1,54 @@
 use itertools::Itertools;
-use std::{collections::HashSet, ops::Add};
+use std::collections::HashSet;
+use crate::{error::{self, ParseError}, point::Point3, search, solution::{Answer, Solution}};
 
-#[derive(Copy, Clone, Eq, Hash, PartialEq)]
-struct Cube {
-    x: i8,
-    y: i8,
-    z: i8,
-}
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = HashSet<Cube>;
 
-impl Cube {
-    fn neighbours(&self) -> Vec<Cube> {
-        vec!(
-            *self + Cube { x: -1, y: 0, z: 0 },
-            *self + Cube { x: 1, y: 0, z: 0 },
-            *self + Cube { x: 0, y: -1, z: 0 },
-            *self + Cube { x: 0, y: 1, z: 0 },
-            *self + Cube { x: 0, y: 0, z: -1 },
-            *self + Cube { x: 0, y: 0, z: 1 },
-        )
+    fn parse(input: &str) -> Result<HashSet<Cube>, ParseError> {
+        parse(input).map_err(|err| err.on_day(Self::DAY))
     }
-}
 
-impl Add for Cube {
-    type Output = Cube;
+    fn part_one(cubes: &HashSet<Cube>) -> Answer {
+        solve_part_one(cubes).into()
+    }
 
-    fn add(self, other: Cube) -> Cube {
-        Cube {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
+    fn part_two(cubes: &HashSet<Cube>) -> Answer {
+        solve(cubes).into()
     }
 }
 
-fn parse(input: &str) -> HashSet<Cube> {
+pub type Cube = Point3;
+
+fn parse(input: &str) -> Result<HashSet<Cube>, ParseError> {
     let mut cubes = HashSet::<Cube>::new();
 
-    input
-        .lines()
-        .for_each(|line| {
-            let (x, y, z) = line
-                .split(",")
-                .map(|n| n.parse().unwrap())
-                .collect_tuple().unwrap();
+    for line in error::lines(input) {
+        let (x, y, z) = line.text
+            .split(',')
+            .collect_tuple()
+            .ok_or_else(|| line.invalid("three comma-separated coordinates"))?;
 
-            cubes.insert(Cube { x, y, z });
-        });
+        cubes.insert(Cube::new(line.parse(x)?, line.parse(y)?, line.parse(z)?));
+    }
 
-    cubes
-}
+    if cubes.is_empty() {
+        return Err(error::end_of_input(input, "a cube"));
+    }
 
-pub fn solve_part_one(input: &str) -> usize {
-    let cubes = parse(input);
+    Ok(cubes)
+}
 
+pub fn solve_part_one(cubes: &HashSet<Cube>) -> usize {
     cubes
         .iter()
         .fold(0, |surfaces, cube| {
             let empty_neighbours = cube
                 .neighbours()
-                .iter()
                 .filter(|neighbour| !cubes.contains(neighbour))
                 .count();
 
@@ -66,16 +56,14 @@ pub fn solve_part_one(input: &str) -> usize {
         })
 }
 
-pub fn solve(input: &str) -> usize {
-    let cubes = parse(input);
-    let outside_cubes = get_outside_cubes(&cubes);
+pub fn solve(cubes: &HashSet<Cube>) -> usize {
+    let outside_cubes = get_outside_cubes(cubes);
 
     cubes
         .iter()
         .fold(0, |surfaces, cube| {
             let outside_neighbours = cube
                 .neighbours()
-                .iter()
                 .filter(|neighbour|
                     outside_cubes.contains(neighbour) && !cubes.contains(neighbour)
                 )
@@ -86,9 +74,6 @@ pub fn solve(input: &str) -> usize {
 }
 
 fn get_outside_cubes(cubes: &HashSet<Cube>) -> HashSet<Cube> {
-    let mut outside = HashSet::<Cube>::new();
-    let mut occupied = HashSet::<Cube>::new();
-
     let min_x = cubes.iter().min_by(|a, b| a.x.cmp(&b.x)).unwrap().x - 1;
     let min_y = cubes.iter().min_by(|a, b| a.y.cmp(&b.y)).unwrap().y - 1;
     let min_z = cubes.iter().min_by(|a, b| a.z.cmp(&b.z)).unwrap().z - 1;
@@ -97,33 +82,48 @@ fn get_outside_cubes(cubes: &HashSet<Cube>) -> HashSet<Cube> {
     let max_y = cubes.iter().max_by(|a, b| a.y.cmp(&b.y)).unwrap().y + 1;
     let max_z = cubes.iter().max_by(|a, b| a.z.cmp(&b.z)).unwrap().z + 1;
 
-    outside.insert(Cube { x: min_x, y: min_y, z: min_z });
-
-    let mut explored_len = 1;
+    let start = Cube::new(min_x, min_y, min_z);
 
-    loop {
-        for cube in outside.clone().iter() {
-            for neighbour in cube.neighbours().iter() {
-                let in_x = neighbour.x >= min_x && neighbour.x <= max_x;
-                let in_y = neighbour.y >= min_y && neighbour.y <= max_y;
-                let in_z = neighbour.z >= min_z && neighbour.z <= max_z;
+    search::distances([start], |cube| cube
+        .neighbours()
+        .filter(move |neighbour| {
+            let in_x = neighbour.x >= min_x && neighbour.x <= max_x;
+            let in_y = neighbour.y >= min_y && neighbour.y <= max_y;
+            let in_z = neighbour.z >= min_z && neighbour.z <= max_z;
 
-                if in_x && in_y && in_z {
-                    if cubes.contains(neighbour) {
-                        occupied.insert(*neighbour);
-                    } else {
-                        outside.insert(*neighbour);
-                    }
-                }
-            }
-        }
-
-        if explored_len == outside.len() + occupied.len() {
-            break
-        }
+            in_x && in_y && in_z && !cubes.contains(neighbour)
+        })
+    )
+        .into_keys()
+        .collect()
+}
 
-        explored_len = outside.len() + occupied.len();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day18::parse(EXAMPLE).unwrap()), 64);
     }
 
-    outside
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day18::parse(EXAMPLE).unwrap()), 58);
+    }
 }
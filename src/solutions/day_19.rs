@@ -1,9 +1,32 @@
-use itertools::Itertools;
+use std::{cmp::{max, min}, fmt};
+use crate::{error::{self, Line, ParseError}, parallel, solution::{Answer, Solution}};
 
-const MINUTES: u8 = 24;
+const MINUTES_ONE: usize = 24;
+const MINUTES: usize = 32;
+const BLUEPRINTS: usize = 3;
 
-#[derive(Debug)]
-enum Resource {
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(blueprints: &Vec<Blueprint>) -> Answer {
+        solve_part_one(blueprints).into()
+    }
+
+    fn part_two(blueprints: &Vec<Blueprint>) -> Answer {
+        solve(blueprints).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Resource {
     Ore,
     Clay,
     Obsidian,
@@ -11,85 +34,404 @@ enum Resource {
 }
 
 impl Resource {
-    fn from_string(resource: &str) -> Resource {
+    fn name(&self) -> &str {
+        match self {
+            Resource::Ore => "ore",
+            Resource::Clay => "clay",
+            Resource::Obsidian => "obsidian",
+            Resource::Geode => "geode",
+        }
+    }
+
+    fn robot(&self) -> &str {
+        match self {
+            Resource::Ore => "ore-collecting robot",
+            Resource::Clay => "clay-collecting robot",
+            Resource::Obsidian => "obsidian-collecting robot",
+            Resource::Geode => "geode-cracking robot",
+        }
+    }
+
+    fn from_string(line: &Line, resource: &str) -> Result<Resource, ParseError> {
         match resource {
-            "ore" => Resource::Ore,
-            "clay" => Resource::Clay,
-            "obsidian" => Resource::Obsidian,
-            "geode" => Resource::Geode,
-            _ => panic!("unknown type!"),
+            "ore" => Ok(Resource::Ore),
+            "clay" => Ok(Resource::Clay),
+            "obsidian" => Ok(Resource::Obsidian),
+            "geode" => Ok(Resource::Geode),
+            _ => Err(line.error(resource, "ore, clay, obsidian or geode")),
         }
     }
 }
 
 #[derive(Debug)]
-struct Robot {
-    resource: Resource,
-    cost: Vec<(Resource, u8)>,
+pub struct Robot {
+    pub resource: Resource,
+    pub cost: Vec<(Resource, u8)>,
 }
 
 #[derive(Debug)]
-struct Blueprint {
-    id: u8,
-    robots: Vec<Robot>,
+pub struct Blueprint {
+    pub id: u8,
+    pub robots: Vec<Robot>,
 }
 
-pub fn solve(input: &str) -> usize {
-    let blueprints = parse(input);
-
+pub fn solve_part_one(blueprints: &[Blueprint]) -> usize {
     blueprints
         .iter()
-        .fold(0, |sum, blueprint| 
-            sum + get_quality(&blueprint)
-        )
+        .zip(get_geode_counts(blueprints, MINUTES_ONE))
+        .map(|(blueprint, geodes)| blueprint.id as usize * geodes)
+        .sum()
+}
+
+pub fn solve(blueprints: &[Blueprint]) -> usize {
+    let blueprints = &blueprints[..min(BLUEPRINTS, blueprints.len())];
+
+    get_geode_counts(blueprints, MINUTES)
+        .iter()
+        .product()
+}
+
+/// Searches the blueprints independently on the `parallel` pool, whose `--threads` workers take
+/// the next unsearched blueprint until there are none left.
+pub fn get_plans(blueprints: &[Blueprint], minutes: usize) -> Vec<Plan> {
+    parallel::map(blueprints, |blueprint| get_plan(blueprint, minutes))
+}
+
+fn get_geode_counts(blueprints: &[Blueprint], minutes: usize) -> Vec<usize> {
+    get_plans(blueprints, minutes)
+        .iter()
+        .map(|plan| plan.geodes)
+        .collect()
+}
+
+/// Amounts of each resource, indexed by `Resource as usize`.
+pub type Amounts = [usize; 4];
+
+const GEODE: usize = Resource::Geode as usize;
+
+const RESOURCES: [Resource; 4] = [Resource::Ore, Resource::Clay, Resource::Obsidian, Resource::Geode];
+
+/// A robot to build, along with the minutes left when building starts.
+type Build = (Resource, usize);
+
+struct Best {
+    geodes: usize,
+    builds: Vec<Build>,
+}
+
+struct Factory {
+    robots: Vec<(Resource, Amounts)>,
+    max_robots: Amounts,
+}
+
+impl Factory {
+    fn new(blueprint: &Blueprint) -> Factory {
+        let mut robots: Vec<(Resource, Amounts)> = blueprint.robots
+            .iter()
+            .map(|robot| {
+                let mut cost = [0; 4];
+                for (resource, amount) in &robot.cost {
+                    cost[*resource as usize] += *amount as usize;
+                }
+                (robot.resource, cost)
+            })
+            .collect();
+
+        // trying geode robots first finds good answers sooner, so more branches get pruned
+        robots.sort_by_key(|robot| usize::MAX - robot.0 as usize);
+
+        // there's no point having more robots of a kind than we can spend in a minute, as we can
+        // only build one robot per minute
+        let mut max_robots = [0, 0, 0, usize::MAX];
+        for (_, cost) in &robots {
+            for resource in 0..GEODE {
+                max_robots[resource] = max(max_robots[resource], cost[resource]);
+            }
+        }
+
+        Factory {
+            robots,
+            max_robots,
+        }
+    }
+
+    fn cost(&self, robot: Resource) -> Amounts {
+        self.robots
+            .iter()
+            .find(|(resource, _)| *resource == robot)
+            .map(|(_, cost)| *cost)
+            .unwrap()
+    }
+
+    /// Minutes spent waiting before `cost` can be paid, or `None` if nothing produces one of the
+    /// resources it needs.
+    fn wait(cost: &Amounts, robots: &Amounts, resources: &Amounts) -> Option<usize> {
+        (0..4).try_fold(0, |wait, resource| {
+            if cost[resource] <= resources[resource] {
+                Some(wait)
+            } else if robots[resource] == 0 {
+                None
+            } else {
+                let needed = cost[resource] - resources[resource];
+                Some(max(wait, needed.div_ceil(robots[resource])))
+            }
+        })
+    }
+
+    /// Finds the most geodes that can be opened, choosing which robot to build next and skipping
+    /// ahead to when it can be afforded rather than going minute by minute.
+    fn search(
+        &self,
+        minutes: usize,
+        robots: Amounts,
+        resources: Amounts,
+        builds: &mut Vec<Build>,
+        best: &mut Best,
+    ) {
+        let geodes = resources[GEODE] + robots[GEODE] * minutes;
+        if geodes > best.geodes {
+            *best = Best { geodes, builds: builds.clone() };
+        }
+
+        // even building a geode robot every remaining minute wouldn't beat the best so far
+        let bound = geodes + minutes * minutes.saturating_sub(1) / 2;
+        if bound <= best.geodes {
+            return;
+        }
+
+        for (robot, cost) in &self.robots {
+            if robots[*robot as usize] >= self.max_robots[*robot as usize] {
+                continue;
+            }
+
+            let wait = match Factory::wait(cost, &robots, &resources) {
+                Some(wait) if wait + 1 < minutes => wait,
+                _ => continue,
+            };
+
+            let mut next_robots = robots;
+            let mut next_resources = resources;
+
+            for resource in 0..4 {
+                next_resources[resource] += robots[resource] * (wait + 1);
+                next_resources[resource] -= cost[resource];
+            }
+            next_robots[*robot as usize] += 1;
+
+            builds.push((*robot, minutes - wait));
+            self.search(minutes - wait - 1, next_robots, next_resources, builds, best);
+            builds.pop();
+        }
+    }
+}
+
+pub struct Minute {
+    pub minute: usize,
+    /// The robot started this minute and what it cost.
+    pub build: Option<(Resource, Amounts)>,
+    /// The robots collecting this minute, not counting one that's being built.
+    pub robots: Amounts,
+    /// What's in the inventory at the end of the minute.
+    pub resources: Amounts,
+}
+
+/// The best build order for a blueprint, minute by minute.
+pub struct Plan {
+    pub id: u8,
+    pub geodes: usize,
+    pub minutes: Vec<Minute>,
+}
+
+pub fn get_plan(blueprint: &Blueprint, minutes: usize) -> Plan {
+    let factory = Factory::new(blueprint);
+
+    let mut robots = [0; 4];
+    robots[Resource::Ore as usize] = 1;
+
+    let mut best = Best { geodes: 0, builds: vec!() };
+    factory.search(minutes, robots, [0; 4], &mut vec!(), &mut best);
+
+    let mut resources = [0; 4];
+    let mut builds = best.builds.iter().peekable();
+
+    let timeline = (1..=minutes)
+        .map(|minute| {
+            let build = builds
+                .next_if(|(_, minutes_left)| minute + minutes_left == minutes + 1)
+                .map(|(robot, _)| (*robot, factory.cost(*robot)));
+
+            let collecting = robots;
+
+            for resource in 0..4 {
+                resources[resource] += robots[resource];
+            }
+
+            if let Some((robot, cost)) = build {
+                for resource in 0..4 {
+                    resources[resource] -= cost[resource];
+                }
+                robots[robot as usize] += 1;
+            }
+
+            Minute {
+                minute,
+                build,
+                robots: collecting,
+                resources,
+            }
+        })
+        .collect();
+
+    Plan {
+        id: blueprint.id,
+        geodes: best.geodes,
+        minutes: timeline,
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 { word.to_string() } else { format!("{}s", word) }
+}
+
+impl fmt::Display for Minute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "== Minute {} ==", self.minute)?;
+
+        if let Some((robot, cost)) = &self.build {
+            let cost = RESOURCES
+                .iter()
+                .filter(|resource| cost[**resource as usize] > 0)
+                .map(|resource| format!("{} {}", cost[*resource as usize], resource.name()))
+                .collect::<Vec<String>>()
+                .join(" and ");
+            let article = if *robot == Resource::Ore || *robot == Resource::Obsidian { "an" } else { "a" };
+
+            writeln!(f, "Spend {} to start building {} {}.", cost, article, robot.robot())?;
+        }
+
+        for resource in RESOURCES {
+            let robots = self.robots[resource as usize];
+            let total = self.resources[resource as usize];
+
+            if robots == 0 {
+                continue;
+            }
+
+            let robot = plural(robots, resource.robot());
+
+            if resource == Resource::Geode {
+                let verb = if robots == 1 { "cracks" } else { "crack" };
+                let geodes = plural(robots, "geode");
+                let open = plural(total, "open geode");
+
+                writeln!(f, "{} {} {} {} {}; you now have {} {}.", robots, robot, verb, robots, geodes, total, open)?;
+            } else {
+                let verb = if robots == 1 { "collects" } else { "collect" };
+                let name = resource.name();
+
+                writeln!(f, "{} {} {} {} {}; you now have {} {}.", robots, robot, verb, robots, name, total, name)?;
+            }
+        }
+
+        if let Some((robot, _)) = &self.build {
+            let count = self.robots[*robot as usize] + 1;
+
+            writeln!(f, "The new {} is ready; you now have {} of them.", robot.robot(), count)?;
+        }
+
+        Ok(())
+    }
 }
 
-fn get_quality(blueprint: &Blueprint) -> usize {
-    let mut robots = vec!(Resource::Ore);
-    0
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Blueprint {}: {} {}", self.id, self.geodes, plural(self.geodes, "geode"))?;
+
+        for minute in &self.minutes {
+            writeln!(f)?;
+            write!(f, "{}", minute)?;
+        }
+
+        Ok(())
+    }
 }
 
-fn parse(input: &str) -> Vec<Blueprint> {
-    input
-        .lines()
+fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    error::lines(input)
         .map(|line| {
-            let (id, robots) = line.split(": ").collect_tuple().unwrap();
-            let id = id.replace("Blueprint ", "").parse().unwrap();
+            let (id, robots) = line.split_once(line.text, ": ")?;
+            let id = line.parse(line.strip_prefix(id, "Blueprint ")?)?;
 
             let robots = robots
+                .trim_end_matches('.')
                 .split(". ")
                 .map(|robot| {
-                    let robot = robot
-                        .replace("Each ", "")
-                        .replace(" robot costs ", ";")
-                        .replace(".", "");
+                    let robot = line.strip_prefix(robot, "Each ")?;
+                    let (resource, cost) = line.split_once(robot, " robot costs ")?;
 
-                    let (resource, cost) = robot.split(";").collect_tuple().unwrap();
-
-                    let resource = Resource::from_string(resource);
+                    let resource = Resource::from_string(&line, resource)?;
 
                     let cost = cost
                         .split(" and ")
                         .map(|c| {
-                            let (amount, resource) = c.split_whitespace().collect_tuple().unwrap();
-                            let amount = amount.parse::<u8>().unwrap();
-                            let resource = Resource::from_string(resource);
-                            (resource, amount)
+                            let (amount, resource) = line.split_once(c, " ")?;
+                            let amount = line.parse::<u8>(amount)?;
+                            let resource = Resource::from_string(&line, resource)?;
+                            Ok((resource, amount))
                         })
-                        .collect();
+                        .collect::<Result<_, _>>()?;
 
-                    Robot {
+                    Ok(Robot {
                         resource,
                         cost,
-                    }
+                    })
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
 
-            Blueprint {
+            Ok(Blueprint {
                 id,
                 robots,
-            }
+            })
         })
         .collect()
 }
- 
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn max_geodes() {
+        let blueprints = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(get_geode_counts(&blueprints, MINUTES_ONE), vec!(9, 12));
+        assert_eq!(get_geode_counts(&blueprints, MINUTES), vec!(56, 62));
+    }
+
+    #[test]
+    fn plan() {
+        let plan = get_plan(&Day19::parse(EXAMPLE).unwrap()[0], MINUTES_ONE);
+        let last = plan.minutes.last().unwrap();
+
+        assert_eq!(plan.minutes.len(), 24);
+        assert_eq!(last.resources[GEODE], 9);
+        assert_eq!(
+            plan.minutes[0].to_string(),
+            "== Minute 1 ==\n1 ore-collecting robot collects 1 ore; you now have 1 ore.\n",
+        );
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day19::parse(EXAMPLE).unwrap()), 33);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day19::parse(EXAMPLE).unwrap()), 3472);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{error::{self, ParseError}, solution::{Answer, Solution}};
+
+const DECRYPTION_KEY: isize = 811589153;
+const ROUNDS: usize = 10;
+
+const GROVE_OFFSETS: [usize; 3] = [1000, 2000, 3000];
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Vec<isize>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(numbers: &Vec<isize>) -> Answer {
+        solve_part_one(numbers).into()
+    }
+
+    fn part_two(numbers: &Vec<isize>) -> Answer {
+        solve(numbers).into()
+    }
+}
+
+fn process(input: &str) -> Result<Vec<isize>, ParseError> {
+    // decrypted, three of the numbers are added up for the grove coordinates
+    let limit = DECRYPTION_KEY * GROVE_OFFSETS.len() as isize;
+
+    let numbers: Vec<isize> = error::lines(input)
+        .map(|line| {
+            let number: isize = line.parse(line.text.trim())?;
+
+            match number.checked_mul(limit) {
+                Some(_) => Ok(number),
+                None => Err(line.invalid("a number small enough to decrypt")),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    if !numbers.contains(&0) {
+        return Err(error::end_of_input(input, "a 0 to find the grove coordinates from"));
+    }
+
+    Ok(numbers)
+}
+
+pub fn solve_part_one(numbers: &[isize]) -> isize {
+    get_coordinates(numbers, 1)
+}
+
+pub fn solve(numbers: &[isize]) -> isize {
+    let numbers: Vec<isize> = numbers
+        .iter()
+        .map(|number| number * DECRYPTION_KEY)
+        .collect();
+
+    get_coordinates(&numbers, ROUNDS)
+}
+
+fn get_coordinates(numbers: &[isize], rounds: usize) -> isize {
+    let mixed = mix(numbers, rounds);
+    let zero = mixed.iter().position(|number| *number == 0).unwrap();
+
+    GROVE_OFFSETS
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+/// Moves each number forwards or backwards around the circular list by its value, in the order
+/// they originally appeared, returning the list starting from wherever it ends up.
+pub fn mix(numbers: &[isize], rounds: usize) -> Vec<isize> {
+    // the list holds indices into `numbers`, as the numbers themselves aren't unique
+    let mut list: Vec<usize> = (0..numbers.len()).collect();
+    let cycle = numbers.len() as isize - 1;
+
+    if cycle < 1 {
+        return numbers.to_vec();
+    }
+
+    for _ in 0..rounds {
+        for (idx, number) in numbers.iter().enumerate() {
+            let from = list.iter().position(|item| *item == idx).unwrap();
+            list.remove(from);
+
+            // a number moving all the way round passes every other number, so moves repeat
+            // every `len - 1` steps rather than every `len`
+            let to = (from as isize + number.rem_euclid(cycle)).rem_euclid(cycle) as usize;
+            list.insert(to, idx);
+        }
+    }
+
+    list
+        .into_iter()
+        .map(|idx| numbers[idx])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1
+2
+-3
+3
+-2
+0
+4";
+
+    #[test]
+    fn mix_once() {
+        let mixed = mix(&Day20::parse(EXAMPLE).unwrap(), 1);
+        let zero = mixed.iter().position(|number| *number == 0).unwrap();
+
+        let from_zero: Vec<isize> = (0..mixed.len())
+            .map(|offset| mixed[(zero + offset) % mixed.len()])
+            .collect();
+
+        assert_eq!(from_zero, vec!(0, 3, -2, 1, 2, -3, 4));
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day20::parse(EXAMPLE).unwrap()), 3);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day20::parse(EXAMPLE).unwrap()), 1623178306);
+    }
+
+    #[test]
+    fn missing_zero() {
+        let err = Day20::parse("1\n2").unwrap_err();
+
+        assert_eq!((err.day, err.line), (20, 3));
+    }
+
+    #[test]
+    fn large_numbers() {
+        let largest = isize::MAX / (DECRYPTION_KEY * 3);
+        let numbers = Day20::parse(&format!("0\n{}\n{}", largest, -largest)).unwrap();
+
+        assert_eq!(solve(&numbers), 0);
+        assert_eq!(Day20::parse(&format!("0\n{}", largest + 1)).unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,486 @@
+use std::{collections::{HashMap, HashSet}, fmt};
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = HashMap<String, Job>;
+
+    fn parse(input: &str) -> Result<HashMap<String, Job>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(monkeys: &HashMap<String, Job>) -> Answer {
+        solve_part_one(monkeys).into()
+    }
+
+    fn part_two(monkeys: &HashMap<String, Job>) -> Answer {
+        solve(monkeys).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Job {
+    Number(i64),
+    Op(String, Op, String),
+}
+
+/// Why a monkey's number can't be worked out exactly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MathError {
+    DivisionByZero,
+    Overflow,
+    /// `humn` ended up multiplied by itself, or dividing something.
+    NotLinear,
+    /// `humn` cancels out of `root`'s comparison, so no one number makes it match.
+    NoUniqueSolution,
+}
+
+impl MathError {
+    fn expected(&self) -> String {
+        match self {
+            MathError::DivisionByZero => "a divisor that isn't zero".to_string(),
+            MathError::Overflow => "numbers small enough to work out exactly".to_string(),
+            MathError::NotLinear => format!(
+                "{} on only one side of a multiplication, and not dividing",
+                HUMAN,
+            ),
+            MathError::NoUniqueSolution => format!("a comparison that {} changes", HUMAN),
+        }
+    }
+}
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Result<Rational, MathError> {
+        if den == 0 {
+            return Err(MathError::DivisionByZero);
+        }
+
+        let divisor = i128::try_from(gcd(num.unsigned_abs(), den.unsigned_abs()))
+            .map_err(|_| MathError::Overflow)?;
+        let (num, den) = (num / divisor, den / divisor);
+
+        if den < 0 {
+            Ok(Rational {
+                num: num.checked_neg().ok_or(MathError::Overflow)?,
+                den: den.checked_neg().ok_or(MathError::Overflow)?,
+            })
+        } else {
+            Ok(Rational { num, den })
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn checked_add(self, other: Rational) -> Result<Rational, MathError> {
+        let num = checked(self.num.checked_mul(other.den))?
+            .checked_add(checked(other.num.checked_mul(self.den))?);
+
+        Rational::new(checked(num)?, checked(self.den.checked_mul(other.den))?)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Result<Rational, MathError> {
+        let negated = Rational {
+            num: checked(other.num.checked_neg())?,
+            den: other.den,
+        };
+
+        self.checked_add(negated)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Result<Rational, MathError> {
+        Rational::new(
+            checked(self.num.checked_mul(other.num))?,
+            checked(self.den.checked_mul(other.den))?,
+        )
+    }
+
+    pub fn checked_div(self, other: Rational) -> Result<Rational, MathError> {
+        if other.is_zero() {
+            return Err(MathError::DivisionByZero);
+        }
+
+        Rational::new(
+            checked(self.num.checked_mul(other.den))?,
+            checked(self.den.checked_mul(other.num))?,
+        )
+    }
+}
+
+fn checked(result: Option<i128>) -> Result<i128, MathError> {
+    result.ok_or(MathError::Overflow)
+}
+
+impl From<i64> for Rational {
+    fn from(number: i64) -> Rational {
+        Rational {
+            num: number as i128,
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{}", self.num, den),
+        }
+    }
+}
+
+impl From<Rational> for Answer {
+    fn from(number: Rational) -> Answer {
+        match (number.den, i64::try_from(number.num)) {
+            (1, Ok(num)) => Answer::Number(num),
+            _ => Answer::Text(number.to_string()),
+        }
+    }
+}
+
+/// `humn * coefficient + constant`, which is what every monkey's number works out as when
+/// `humn` is unknown, as long as it's never multiplied by itself or dividing something.
+#[derive(Clone, Copy, Debug)]
+struct Linear {
+    coefficient: Rational,
+    constant: Rational,
+}
+
+impl Linear {
+    fn constant(number: Rational) -> Linear {
+        Linear {
+            coefficient: Rational::from(0),
+            constant: number,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    fn apply(self, op: Op, other: Linear) -> Result<Linear, MathError> {
+        let (coefficient, constant) = match op {
+            Op::Add => (
+                self.coefficient.checked_add(other.coefficient)?,
+                self.constant.checked_add(other.constant)?,
+            ),
+            Op::Sub => (
+                self.coefficient.checked_sub(other.coefficient)?,
+                self.constant.checked_sub(other.constant)?,
+            ),
+            Op::Mul if self.is_constant() => (
+                other.coefficient.checked_mul(self.constant)?,
+                other.constant.checked_mul(self.constant)?,
+            ),
+            Op::Mul if other.is_constant() => (
+                self.coefficient.checked_mul(other.constant)?,
+                self.constant.checked_mul(other.constant)?,
+            ),
+            Op::Div if other.is_constant() => (
+                self.coefficient.checked_div(other.constant)?,
+                self.constant.checked_div(other.constant)?,
+            ),
+            _ => return Err(MathError::NotLinear),
+        };
+
+        Ok(Linear {
+            coefficient,
+            constant,
+        })
+    }
+}
+
+fn process(input: &str) -> Result<HashMap<String, Job>, ParseError> {
+    let mut monkeys = HashMap::new();
+    let mut lines = HashMap::new();
+    let mut references = vec!();
+
+    for line in error::lines(input) {
+        let (name, job) = line.split_once(line.text, ": ")?;
+
+        let job = match job.split(' ').collect::<Vec<&str>>()[..] {
+            [number] => Job::Number(line.parse(number)?),
+            [left, op, right] => {
+                let op = match op {
+                    "+" => Op::Add,
+                    "-" => Op::Sub,
+                    "*" => Op::Mul,
+                    "/" => Op::Div,
+                    _ => return Err(line.error(op, "+, -, * or /")),
+                };
+
+                references.push((line, left));
+                references.push((line, right));
+
+                Job::Op(left.to_string(), op, right.to_string())
+            },
+            _ => return Err(line.error(job, "a number or an operation")),
+        };
+
+        monkeys.insert(name.to_string(), job);
+        lines.insert(name, line);
+    }
+
+    for (line, name) in references {
+        if !monkeys.contains_key(name) {
+            return Err(line.error(name, "a known monkey"));
+        }
+    }
+
+    for name in [ROOT, HUMAN] {
+        if !monkeys.contains_key(name) {
+            return Err(error::end_of_input(input, &format!("monkey {}", name)));
+        }
+    }
+
+    if let Job::Number(_) = monkeys[ROOT] {
+        return Err(lines[ROOT].invalid("an operation comparing two monkeys"));
+    }
+
+    let mut on_stack = HashSet::new();
+    let mut done = HashSet::new();
+
+    if let Some((name, reference)) = find_cycle(&monkeys, ROOT, &mut on_stack, &mut done) {
+        let line: Line = lines[name];
+        let idx = line.text.rfind(reference).unwrap();
+        let expected = format!("a monkey whose number doesn't depend on {}", name);
+
+        return Err(line.error(&line.text[idx..idx + reference.len()], &expected));
+    }
+
+    // part one only fails on the numbers the monkeys yell, so check they work out up front
+    if let Err((name, err)) = evaluate(&monkeys, ROOT, false, &mut HashMap::new()) {
+        return Err(lines[name].invalid(&err.expected()));
+    }
+
+    Ok(monkeys)
+}
+
+/// Looks for a monkey whose job refers to a monkey that's waiting on it, returning that monkey's
+/// name and the name it refers to.
+fn find_cycle<'a>(
+    monkeys: &'a HashMap<String, Job>,
+    name: &'a str,
+    on_stack: &mut HashSet<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<(&'a str, &'a str)> {
+    if let Some(Job::Op(left, _, right)) = monkeys.get(name) {
+        on_stack.insert(name);
+
+        for reference in [left.as_str(), right.as_str()] {
+            if on_stack.contains(reference) {
+                return Some((name, reference));
+            }
+
+            if !done.contains(reference) {
+                if let Some(cycle) = find_cycle(monkeys, reference, on_stack, done) {
+                    return Some(cycle);
+                }
+            }
+        }
+
+        on_stack.remove(name);
+    }
+
+    done.insert(name);
+
+    None
+}
+
+pub fn solve_part_one(monkeys: &HashMap<String, Job>) -> Rational {
+    evaluate(monkeys, ROOT, false, &mut HashMap::new())
+        .expect("the monkeys' numbers are checked while parsing")
+        .constant
+}
+
+/// What `humn` needs to yell, if there's exactly one number that works out.
+pub fn solve(monkeys: &HashMap<String, Job>) -> Option<Rational> {
+    get_human(monkeys).ok()
+}
+
+/// Works out what `humn` needs to yell for `root`'s two monkeys to match, or the monkey where
+/// that goes wrong.
+fn get_human(monkeys: &HashMap<String, Job>) -> Result<Rational, (&str, MathError)> {
+    let (left, right) = match monkeys.get(ROOT) {
+        Some(Job::Op(left, _, right)) => (left, right),
+        _ => panic!("{} doesn't compare two monkeys!", ROOT),
+    };
+
+    let mut known = HashMap::new();
+    let left = evaluate(monkeys, left, true, &mut known)?;
+    let right = evaluate(monkeys, right, true, &mut known)?;
+
+    // left.coefficient * humn + left.constant = right.coefficient * humn + right.constant
+    let solve = || {
+        let coefficient = left.coefficient.checked_sub(right.coefficient)?;
+
+        if coefficient.is_zero() {
+            return Err(MathError::NoUniqueSolution);
+        }
+
+        right.constant.checked_sub(left.constant)?.checked_div(coefficient)
+    };
+
+    solve().map_err(|err| (ROOT, err))
+}
+
+/// Works out a monkey's number, in terms of `humn` if it's unknown, or the monkey whose job
+/// can't be done. Monkeys can be waited on by more than one other, so the numbers already worked
+/// out are kept in `known`.
+fn evaluate<'a>(
+    monkeys: &'a HashMap<String, Job>,
+    name: &'a str,
+    human_unknown: bool,
+    known: &mut HashMap<&'a str, Linear>,
+) -> Result<Linear, (&'a str, MathError)> {
+    if let Some(number) = known.get(name) {
+        return Ok(*number);
+    }
+
+    let number = if human_unknown && name == HUMAN {
+        Linear {
+            coefficient: Rational::from(1),
+            constant: Rational::from(0),
+        }
+    } else {
+        match monkeys.get(name).unwrap() {
+            Job::Number(number) => Linear::constant(Rational::from(*number)),
+            Job::Op(left, op, right) => {
+                let left = evaluate(monkeys, left, human_unknown, known)?;
+                let right = evaluate(monkeys, right, human_unknown, known)?;
+
+                left.apply(*op, right).map_err(|err| (name, err))?
+            },
+        }
+    };
+
+    known.insert(name, number);
+
+    Ok(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day21::parse(EXAMPLE).unwrap()), Rational::from(152));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day21::parse(EXAMPLE).unwrap()), Some(Rational::from(301)));
+    }
+
+    #[test]
+    fn exact_division() {
+        let monkeys = Day21::parse("root: humn / half\nhumn: 3\nhalf: 2").unwrap();
+
+        assert_eq!(solve_part_one(&monkeys), Rational::new(3, 2).unwrap());
+        assert_eq!(Answer::from(solve_part_one(&monkeys)), Answer::Text("3/2".to_string()));
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        let err = Day21::parse("root: humn + abcd\nhumn: 5").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (21, 1, 14));
+    }
+
+    #[test]
+    fn cycle() {
+        let input = "root: aaaa + bbbb\naaaa: bbbb + humn\nbbbb: aaaa * humn\nhumn: 1";
+        let err = Day21::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 7, "aaaa"));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let err = Day21::parse("root: humn / zero\nzero: 0\nhumn: 3").unwrap_err();
+
+        assert_eq!((err.line, err.expected.as_str()), (1, "a divisor that isn't zero"));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut input = "root: m0 + humn\nhumn: 1\nm2: 9223372036854775807".to_string();
+
+        for i in 0..2 {
+            input += &format!("\nm{}: m{} * m{}", i, i + 1, i + 1);
+        }
+
+        let err = Day21::parse(&input).unwrap_err();
+
+        assert_eq!(err.expected, "numbers small enough to work out exactly");
+    }
+
+    #[test]
+    fn unsolvable_part_two() {
+        let squared = Day21::parse("root: humn * humn\nhumn: 5").unwrap();
+        let cancelled = Day21::parse("root: a - a\na: humn + one\none: 1\nhumn: 5").unwrap();
+
+        assert_eq!(solve_part_one(&squared), Rational::from(25));
+        assert_eq!(Day21::part_two(&squared), Answer::Unsolved);
+        assert_eq!(solve_part_one(&cancelled), Rational::from(0));
+        assert_eq!(Day21::part_two(&cancelled), Answer::Unsolved);
+    }
+
+    #[test]
+    fn shared_monkeys() {
+        let mut input = "root: m0 + humn\nhumn: 1\nm40: 1".to_string();
+
+        for i in 0..40 {
+            input += &format!("\nm{}: m{} - m{}", i, i + 1, i + 1);
+        }
+
+        let monkeys = Day21::parse(&input).unwrap();
+
+        assert_eq!(solve_part_one(&monkeys), Rational::from(1));
+        assert_eq!(solve(&monkeys), Some(Rational::from(0)));
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::VecDeque;
+use crate::{error::{self, Line, ParseError}, point::{Dir, Point, Point3}, solution::{Answer, Solution}};
+
+/// Moves for each facing, in the order the password numbers them: right, down, left, up.
+const DIRS: [Dir; 4] = [Dir::Right, Dir::Down, Dir::Left, Dir::Up];
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = (Board, Vec<Step>);
+
+    fn parse(input: &str) -> Result<(Board, Vec<Step>), ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one((board, steps): &(Board, Vec<Step>)) -> Answer {
+        solve_part_one(board, steps).into()
+    }
+
+    fn part_two((board, steps): &(Board, Vec<Step>)) -> Answer {
+        solve(board, steps).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug)]
+pub struct Board {
+    pub rows: Vec<Vec<Tile>>,
+}
+
+impl Board {
+    fn get(&self, x: isize, y: isize) -> Tile {
+        if x < 0 || y < 0 {
+            return Tile::Void;
+        }
+
+        self.rows
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or(Tile::Void)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct State {
+    x: isize,
+    y: isize,
+    facing: usize,
+}
+
+impl State {
+    fn moved(&self) -> State {
+        let Point { x: dx, y: dy } = DIRS[self.facing].delta();
+
+        State {
+            x: self.x + dx,
+            y: self.y + dy,
+            facing: self.facing,
+        }
+    }
+}
+
+fn process(input: &str) -> Result<(Board, Vec<Step>), ParseError> {
+    let lines: Vec<Line> = error::lines(input).collect();
+
+    let split = lines
+        .iter()
+        .position(|line| line.text.is_empty())
+        .ok_or_else(|| error::end_of_input(input, "a blank line after the board"))?;
+
+    let rows = lines[..split]
+        .iter()
+        .map(|line| line.text
+            .char_indices()
+            .map(|(idx, c)| match c {
+                ' ' => Ok(Tile::Void),
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                _ => Err(line.error(&line.text[idx..idx + c.len_utf8()], "a space, . or #")),
+            })
+            .collect::<Result<Vec<Tile>, ParseError>>()
+        )
+        .collect::<Result<Vec<Vec<Tile>>, ParseError>>()?;
+
+    if !rows.first().is_some_and(|row| row.contains(&Tile::Open)) {
+        return Err(lines[0].invalid("an open tile to start from"));
+    }
+
+    let path = lines
+        .get(split + 1)
+        .ok_or_else(|| error::end_of_input(input, "the path"))?;
+
+    Ok((Board { rows }, get_steps(path)?))
+}
+
+fn get_steps(line: &Line) -> Result<Vec<Step>, ParseError> {
+    let text = line.text.trim_end();
+    let mut steps = vec!();
+    let mut digits_start = None;
+
+    for (idx, c) in text.char_indices() {
+        if c.is_ascii_digit() {
+            digits_start.get_or_insert(idx);
+            continue;
+        }
+
+        if let Some(start) = digits_start.take() {
+            steps.push(Step::Forward(line.parse(&text[start..idx])?));
+        }
+
+        match c {
+            'L' => steps.push(Step::Left),
+            'R' => steps.push(Step::Right),
+            _ => return Err(line.error(&text[idx..idx + c.len_utf8()], "a number, L or R")),
+        }
+    }
+
+    if let Some(start) = digits_start {
+        steps.push(Step::Forward(line.parse(&text[start..])?));
+    }
+
+    Ok(steps)
+}
+
+pub fn solve_part_one(board: &Board, steps: &[Step]) -> usize {
+    walk(board, steps, |state| wrap_flat(board, state))
+}
+
+/// The password when the board is folded into a cube, if it folds into one.
+pub fn solve(board: &Board, steps: &[Step]) -> Option<usize> {
+    let cube = Cube::fold(board)?;
+
+    Some(walk(board, steps, |state| cube.wrap(state)))
+}
+
+/// Follows the path, calling `wrap` to find where we end up whenever we'd step off the board, and
+/// returns the password for where we stop.
+fn walk(board: &Board, steps: &[Step], wrap: impl Fn(State) -> State) -> usize {
+    let start = board.rows[0].iter().position(|tile| *tile == Tile::Open).unwrap();
+
+    let mut state = State {
+        x: start as isize,
+        y: 0,
+        facing: 0,
+    };
+
+    for step in steps {
+        match step {
+            Step::Left => state.facing = (state.facing + 3) % 4,
+            Step::Right => state.facing = (state.facing + 1) % 4,
+            Step::Forward(n) => {
+                for _ in 0..*n {
+                    let mut next = state.moved();
+
+                    if board.get(next.x, next.y) == Tile::Void {
+                        next = wrap(state);
+                    }
+
+                    if board.get(next.x, next.y) == Tile::Wall {
+                        break;
+                    }
+
+                    state = next;
+                }
+            },
+        }
+    }
+
+    1000 * (state.y as usize + 1) + 4 * (state.x as usize + 1) + state.facing
+}
+
+/// Wraps round to the other side of the row or column.
+fn wrap_flat(board: &Board, state: State) -> State {
+    let Point { x: dx, y: dy } = DIRS[state.facing].delta();
+    let (mut x, mut y) = (state.x, state.y);
+
+    while board.get(x - dx, y - dy) != Tile::Void {
+        x -= dx;
+        y -= dy;
+    }
+
+    State { x, y, ..state }
+}
+
+/// A face of the net, along with which way it points once folded: `normal` points out of the
+/// cube, and `right` and `down` are the directions that moving right and down on the net go.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    x: isize,
+    y: isize,
+    normal: Point3,
+    right: Point3,
+    down: Point3,
+}
+
+impl Face {
+    /// The direction each facing moves in once folded.
+    fn dir(&self, facing: usize) -> Point3 {
+        match facing {
+            0 => self.right,
+            1 => self.down,
+            2 => -self.right,
+            _ => -self.down,
+        }
+    }
+
+    /// The face next to this one on the net, folded over the shared edge.
+    fn neighbour(&self, facing: usize) -> Face {
+        let Point { x: dx, y: dy } = DIRS[facing].delta();
+        let (normal, right, down) = match facing {
+            0 => (self.right, -self.normal, self.down),
+            1 => (self.down, self.right, -self.normal),
+            2 => (-self.right, self.normal, self.down),
+            _ => (-self.down, self.right, self.normal),
+        };
+
+        Face {
+            x: self.x + dx,
+            y: self.y + dy,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+struct Cube {
+    size: isize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Folds the net up by walking across it from the first face, turning each face over the
+    /// edge it shares with the face we reached it from.
+    fn fold(board: &Board) -> Option<Cube> {
+        let tiles = board.rows
+            .iter()
+            .flatten()
+            .filter(|tile| **tile != Tile::Void)
+            .count();
+
+        let size = (1..).find(|size| 6 * size * size >= tiles)? as isize;
+        if 6 * size * size != tiles as isize {
+            return None;
+        }
+
+        let is_face = |x: isize, y: isize| board.get(x * size, y * size) != Tile::Void;
+
+        let first = Face {
+            x: (0..).find(|x| is_face(*x, 0))?,
+            y: 0,
+            normal: Point3::new(0, 0, 1),
+            right: Point3::new(1, 0, 0),
+            down: Point3::new(0, 1, 0),
+        };
+
+        let mut faces = vec!(first);
+        let mut queue = VecDeque::from([first]);
+
+        while let Some(face) = queue.pop_front() {
+            for facing in 0..4 {
+                let next = face.neighbour(facing);
+
+                let is_new = !faces.iter().any(|face| (face.x, face.y) == (next.x, next.y));
+
+                if next.x >= 0 && next.y >= 0 && is_face(next.x, next.y) && is_new {
+                    faces.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let distinct = faces
+            .iter()
+            .all(|a| faces.iter().filter(|b| a.normal == b.normal).count() == 1);
+
+        if faces.len() != 6 || !distinct {
+            return None;
+        }
+
+        Some(Cube {
+            size,
+            faces,
+        })
+    }
+
+    fn face_at(&self, x: isize, y: isize) -> &Face {
+        self.faces
+            .iter()
+            .find(|face| (face.x, face.y) == (x.div_euclid(self.size), y.div_euclid(self.size)))
+            .unwrap()
+    }
+
+    /// Steps over the edge of the face we're on onto the face it's joined to once folded.
+    ///
+    /// Positions are worked out in 3D with the cube centred on the origin and every tile two
+    /// units wide, so tile centres are whole numbers: a tile's centre is `size` out along its
+    /// face's normal, and the centres of tiles along an edge are `size - 1` out towards it.
+    fn wrap(&self, state: State) -> State {
+        let size = self.size;
+        let face = self.face_at(state.x, state.y);
+        let dir = face.dir(state.facing);
+
+        let next_face = self.faces.iter().find(|next| next.normal == dir).unwrap();
+
+        let local_x = state.x - face.x * size;
+        let local_y = state.y - face.y * size;
+
+        let pos = [
+            face.normal * size,
+            face.right * (2 * local_x + 1 - size),
+            face.down * (2 * local_y + 1 - size),
+        ]
+            .into_iter()
+            .fold(Point3::default(), |sum, part| sum + part);
+
+        // going over the edge moves us one unit further along `dir` and one back from the normal
+        let next_pos = pos + dir - face.normal;
+
+        let next_x = (next_pos.dot(next_face.right) + size - 1) / 2;
+        let next_y = (next_pos.dot(next_face.down) + size - 1) / 2;
+
+        let heading = -face.normal;
+        let facing = (0..4).find(|facing| next_face.dir(*facing) == heading).unwrap();
+
+        State {
+            x: next_face.x * size + next_x,
+            y: next_face.y * size + next_y,
+            facing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "        ...#\n",
+        "        .#..\n",
+        "        #...\n",
+        "        ....\n",
+        "...#.......#\n",
+        "........#...\n",
+        "..#....#....\n",
+        "..........#.\n",
+        "        ...#....\n",
+        "        .....#..\n",
+        "        .#......\n",
+        "        ......#.\n",
+        "\n",
+        "10R5L5R10L4R5L5\n",
+    );
+
+    /// An empty board in the other common layout, with faces three tiles wide.
+    const NET: &str = concat!(
+        "   ......\n",
+        "   ......\n",
+        "   ......\n",
+        "   ...\n",
+        "   ...\n",
+        "   ...\n",
+        "......\n",
+        "......\n",
+        "......\n",
+        "...\n",
+        "...\n",
+        "...\n",
+        "\n",
+        "1\n",
+    );
+
+    #[test]
+    fn part_one() {
+        let (board, steps) = Day22::parse(EXAMPLE).unwrap();
+
+        assert_eq!(solve_part_one(&board, &steps), 6032);
+    }
+
+    #[test]
+    fn part_two() {
+        let (board, steps) = Day22::parse(EXAMPLE).unwrap();
+
+        assert_eq!(solve(&board, &steps), Some(5031));
+    }
+
+    #[test]
+    fn round_the_cube() {
+        for input in [EXAMPLE, NET] {
+            let (board, _) = Day22::parse(input).unwrap();
+            let cube = Cube::fold(&board).unwrap();
+
+            // going round four faces in any direction from anywhere brings us back to the start
+            for (y, row) in board.rows.iter().enumerate() {
+                for (x, _) in row.iter().enumerate().filter(|(_, tile)| **tile != Tile::Void) {
+                    for facing in 0..4 {
+                        let start = State { x: x as isize, y: y as isize, facing };
+                        let mut state = start;
+
+                        for _ in 0..4 * cube.size {
+                            let next = state.moved();
+                            state = if board.get(next.x, next.y) == Tile::Void { cube.wrap(state) } else { next };
+                        }
+
+                        assert_eq!(state, start);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bad_step() {
+        let err = Day22::parse("..\n..\n\n10X").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (22, 4, 3));
+    }
+
+    #[test]
+    fn not_a_cube() {
+        let (board, steps) = Day22::parse("..\n..\n\n1").unwrap();
+
+        assert_eq!(solve_part_one(&board, &steps), 1008);
+        assert_eq!(solve(&board, &steps), None);
+    }
+}
@@ -0,0 +1,156 @@
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use crate::{error::{self, ParseError}, point::{Compass, Point}, solution::{Answer, Solution}};
+
+const ROUNDS: usize = 10;
+
+/// The directions elves consider moving in, in their starting order, each followed by the
+/// directions that need to be empty to move that way.
+const PROPOSALS: [[Compass; 3]; 4] = [
+    [Compass::N, Compass::NE, Compass::NW],
+    [Compass::S, Compass::SE, Compass::SW],
+    [Compass::W, Compass::NW, Compass::SW],
+    [Compass::E, Compass::NE, Compass::SE],
+];
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = HashSet<Point>;
+
+    fn parse(input: &str) -> Result<HashSet<Point>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(elves: &HashSet<Point>) -> Answer {
+        solve_part_one(elves).into()
+    }
+
+    fn part_two(elves: &HashSet<Point>) -> Answer {
+        solve(elves).into()
+    }
+}
+
+fn process(input: &str) -> Result<HashSet<Point>, ParseError> {
+    let mut elves = HashSet::new();
+
+    for (y, line) in error::lines(input).enumerate() {
+        for (x, c) in line.text.char_indices() {
+            match c {
+                '#' => {
+                    elves.insert(Point::new(x as isize, y as isize));
+                },
+                '.' => (),
+                _ => return Err(line.error(&line.text[x..x + c.len_utf8()], "# or .")),
+            }
+        }
+    }
+
+    if elves.is_empty() {
+        return Err(error::end_of_input(input, "an elf"));
+    }
+
+    Ok(elves)
+}
+
+pub fn solve_part_one(elves: &HashSet<Point>) -> usize {
+    let mut elves = elves.clone();
+
+    for round in 0..ROUNDS {
+        spread(&mut elves, round);
+    }
+
+    let (min_x, max_x) = elves.iter().map(|elf| elf.x).minmax().into_option().unwrap();
+    let (min_y, max_y) = elves.iter().map(|elf| elf.y).minmax().into_option().unwrap();
+
+    ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize - elves.len()
+}
+
+pub fn solve(elves: &HashSet<Point>) -> usize {
+    let mut elves = elves.clone();
+    let mut round = 0;
+
+    while spread(&mut elves, round) {
+        round += 1;
+    }
+
+    round + 1
+}
+
+/// Runs a round of the process, returning whether any elf moved.
+fn spread(elves: &mut HashSet<Point>, round: usize) -> bool {
+    let mut proposals: HashMap<Point, Vec<Point>> = HashMap::new();
+
+    for elf in elves.iter() {
+        let is_alone = elf.adjacent().all(|other| !elves.contains(&other));
+
+        if is_alone {
+            continue;
+        }
+
+        let proposal = (0..PROPOSALS.len())
+            .map(|i| PROPOSALS[(round + i) % PROPOSALS.len()])
+            .find(|checks| checks.iter().all(|dir| !elves.contains(&(*elf + dir.delta()))));
+
+        if let Some(checks) = proposal {
+            proposals.entry(*elf + checks[0].delta()).or_default().push(*elf);
+        }
+    }
+
+    let mut moved = false;
+
+    for (target, proposers) in proposals {
+        if let [elf] = proposers[..] {
+            elves.remove(&elf);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    const SMALL_EXAMPLE: &str = "\
+.....
+..##.
+..#..
+.....
+..##.
+.....";
+
+    #[test]
+    fn small_example() {
+        let mut elves = Day23::parse(SMALL_EXAMPLE).unwrap();
+
+        for round in 0..3 {
+            spread(&mut elves, round);
+        }
+
+        assert_eq!(elves, Day23::parse("..#..\n....#\n#....\n....#\n.....\n..#..").unwrap());
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day23::parse(EXAMPLE).unwrap()), 110);
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day23::parse(EXAMPLE).unwrap()), 20);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{error::{self, Line, ParseError}, point::{Dir, Point}, search, solution::{Answer, Solution}};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Valley;
+
+    fn parse(input: &str) -> Result<Valley, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(valley: &Valley) -> Answer {
+        solve_part_one(valley).into()
+    }
+
+    fn part_two(valley: &Valley) -> Answer {
+        solve(valley).into()
+    }
+}
+
+/// Positions are relative to the inside of the walls, so the entrance is at `y = -1` and the exit
+/// at `y = height`.
+type Coord = Point;
+
+#[derive(Debug)]
+pub struct Valley {
+    pub width: isize,
+    pub height: isize,
+    pub blizzards: Vec<(Coord, Dir)>,
+    pub start: Coord,
+    pub end: Coord,
+}
+
+/// Where the blizzards are at each minute, which repeats once every blizzard is back where it
+/// started.
+struct Blizzards {
+    period: usize,
+    occupied: Vec<Vec<bool>>,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl Blizzards {
+    fn new(valley: &Valley) -> Blizzards {
+        let (width, height) = (valley.width as usize, valley.height as usize);
+        let period = width * height / gcd(width, height);
+
+        let occupied = (0..period as isize)
+            .map(|minute| {
+                let mut occupied = vec![false; width * height];
+
+                for (pos, dir) in &valley.blizzards {
+                    let Point { x, y } = *pos + dir.delta() * minute;
+                    let (x, y) = (x.rem_euclid(valley.width), y.rem_euclid(valley.height));
+
+                    occupied[(y * valley.width + x) as usize] = true;
+                }
+
+                occupied
+            })
+            .collect();
+
+        Blizzards {
+            period,
+            occupied,
+        }
+    }
+
+    fn is_clear(&self, valley: &Valley, pos: Coord, minute: usize) -> bool {
+        if pos == valley.start || pos == valley.end {
+            return true;
+        }
+
+        let Point { x, y } = pos;
+
+        let inside = x >= 0 && x < valley.width && y >= 0 && y < valley.height;
+
+        inside && !self.occupied[minute % self.period][(y * valley.width + x) as usize]
+    }
+}
+
+fn get_gap(line: &Line) -> Result<isize, ParseError> {
+    let gaps: Vec<usize> = line.text.match_indices('.').map(|(idx, _)| idx).collect();
+
+    match gaps[..] {
+        [gap] if gap == 0 || gap == line.text.len() - 1 => {
+            Err(line.error(&line.text[gap..gap + 1], "a gap between the corners"))
+        },
+        [gap] => Ok(gap as isize - 1),
+        _ => Err(line.invalid("a wall with one gap")),
+    }
+}
+
+fn process(input: &str) -> Result<Valley, ParseError> {
+    let lines: Vec<Line> = error::lines(input).collect();
+
+    if lines.len() < 3 {
+        return Err(error::end_of_input(input, "a valley with walls round it"));
+    }
+
+    let width = lines[0].text.len();
+    let height = lines.len() - 2;
+
+    // the blizzards need somewhere to blow between the side walls
+    if width < 3 {
+        return Err(lines[0].invalid("a valley at least one tile wide"));
+    }
+
+    let mut blizzards = vec!();
+
+    for (y, line) in lines.iter().enumerate() {
+        if line.text.len() != width {
+            return Err(line.invalid(&format!("a row {} wide", width)));
+        }
+
+        if y == 0 || y == lines.len() - 1 {
+            continue;
+        }
+
+        for (x, c) in line.text.char_indices() {
+            let is_wall = x == 0 || x == width - 1;
+
+            let dir = match c {
+                '#' if is_wall => continue,
+                '.' if !is_wall => continue,
+                '^' if !is_wall => Dir::Up,
+                'v' if !is_wall => Dir::Down,
+                '<' if !is_wall => Dir::Left,
+                '>' if !is_wall => Dir::Right,
+                _ if is_wall => return Err(line.error(&line.text[x..x + c.len_utf8()], "#")),
+                _ => return Err(line.error(&line.text[x..x + c.len_utf8()], "., ^, v, < or >")),
+            };
+
+            blizzards.push((Point::new(x as isize - 1, y as isize - 1), dir));
+        }
+    }
+
+    Ok(Valley {
+        width: width as isize - 2,
+        height: height as isize,
+        blizzards,
+        start: Point::new(get_gap(&lines[0])?, -1),
+        end: Point::new(get_gap(&lines[lines.len() - 1])?, height as isize),
+    })
+}
+
+pub fn solve_part_one(valley: &Valley) -> Option<usize> {
+    let blizzards = Blizzards::new(valley);
+
+    cross(valley, &blizzards, valley.start, valley.end, 0)
+}
+
+pub fn solve(valley: &Valley) -> Option<usize> {
+    let blizzards = Blizzards::new(valley);
+
+    let there = cross(valley, &blizzards, valley.start, valley.end, 0)?;
+    let back = cross(valley, &blizzards, valley.end, valley.start, there)?;
+
+    cross(valley, &blizzards, valley.start, valley.end, back)
+}
+
+/// Finds the earliest minute we can reach `to` by, leaving `from` at minute `start`, if the
+/// blizzards ever let us through.
+///
+/// This is a breadth-first search over positions at each minute, where waiting where we are is
+/// one of the moves. As the blizzards repeat, being somewhere at a minute is the same as being
+/// there a whole period later, so the search only needs to track the minute within the period.
+fn cross(
+    valley: &Valley,
+    blizzards: &Blizzards,
+    from: Coord,
+    to: Coord,
+    start: usize,
+) -> Option<usize> {
+    let path = search::bfs(
+        [(from, start % blizzards.period)],
+        |(pos, minute)| {
+            let next_minute = (minute + 1) % blizzards.period;
+
+            std::iter::once(*pos)
+                .chain(pos.neighbours())
+                .filter(move |next| blizzards.is_clear(valley, *next, next_minute))
+                .map(move |next| (next, next_minute))
+        },
+        |(pos, _)| *pos == to,
+    );
+
+    path.map(|path| start + path.cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day24::parse(EXAMPLE).unwrap()), Some(18));
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve(&Day24::parse(EXAMPLE).unwrap()), Some(54));
+    }
+
+    #[test]
+    fn blizzards_repeat() {
+        let valley = Day24::parse(EXAMPLE).unwrap();
+        let blizzards = Blizzards::new(&valley);
+
+        assert_eq!(blizzards.period, 12);
+        assert!(!blizzards.is_clear(&valley, Point::new(0, 0), 0));
+        assert!(blizzards.is_clear(&valley, Point::new(0, 0), 1));
+    }
+
+    #[test]
+    fn bad_blizzard() {
+        let err = Day24::parse("#.###\n#>x.#\n###.#").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (24, 2, 3));
+    }
+
+    #[test]
+    fn no_way_through() {
+        assert_eq!(solve_part_one(&Day24::parse("#.#\n#^#\n#.#").unwrap()), None);
+    }
+
+    #[test]
+    fn bad_walls() {
+        assert_eq!(Day24::parse(".#\n##\n#.").unwrap_err().line, 1);
+
+        let err = Day24::parse("#.##\n#..#\n.###").unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "."));
+    }
+}
@@ -0,0 +1,283 @@
+use num_bigint::BigInt;
+use std::{cmp::max, fmt, iter::Sum, ops::Add, str::FromStr};
+use crate::{error::{self, Line, ParseError}, solution::{Answer, Solution}};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = Vec<Snafu>;
+
+    fn parse(input: &str) -> Result<Vec<Snafu>, ParseError> {
+        process(input).map_err(|err| err.on_day(Self::DAY))
+    }
+
+    fn part_one(numbers: &Vec<Snafu>) -> Answer {
+        solve_part_one(numbers).into()
+    }
+
+    fn part_two(_numbers: &Vec<Snafu>) -> Answer {
+        // the last day only has one puzzle
+        Answer::Unsolved
+    }
+}
+
+/// A number in balanced base five, where each digit is from -2 to 2.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snafu {
+    /// Least significant first, with no trailing zeros, so zero has no digits.
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    fn new(mut digits: Vec<i8>) -> Snafu {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+
+    pub fn from_line(line: &Line) -> Result<Snafu, ParseError> {
+        let text = line.text.trim_end();
+
+        if text.is_empty() {
+            return Err(line.invalid("a SNAFU number"));
+        }
+
+        let digits = text
+            .char_indices()
+            .rev()
+            .map(|(idx, c)| match c {
+                '2' => Ok(2),
+                '1' => Ok(1),
+                '0' => Ok(0),
+                '-' => Ok(-1),
+                '=' => Ok(-2),
+                _ => Err(line.error(&text[idx..idx + c.len_utf8()], "2, 1, 0, - or =")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Snafu::new(digits))
+    }
+
+    /// The number as an `i64`, or `None` if it's too big to fit.
+    pub fn to_i64(&self) -> Option<i64> {
+        // the higher digits alone can be out of range when the lower ones bring it back in
+        let number = self.digits
+            .iter()
+            .rev()
+            .try_fold(0i128, |number, digit| number.checked_mul(5)?.checked_add(*digit as i128))?;
+
+        i64::try_from(number).ok()
+    }
+}
+
+/// The lowest balanced base five digit of a number, given the number's remainder when divided by
+/// five, which can be negative.
+fn split_digit(rem: i64) -> i8 {
+    match rem.rem_euclid(5) {
+        digit @ 0..=2 => digit as i8,
+        digit => digit as i8 - 5,
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(number: i64) -> Snafu {
+        // i128 so that rounding to the nearest multiple of five can't overflow at the extremes
+        let mut number = number as i128;
+        let mut digits = vec!();
+
+        while number != 0 {
+            let digit = split_digit((number % 5) as i64);
+            digits.push(digit);
+            number = (number - digit as i128) / 5;
+        }
+
+        Snafu::new(digits)
+    }
+}
+
+impl From<&BigInt> for Snafu {
+    fn from(number: &BigInt) -> Snafu {
+        let zero = BigInt::from(0);
+        let mut number = number.clone();
+        let mut digits = vec!();
+
+        while number != zero {
+            let rem = i64::try_from(&number % 5).unwrap();
+            let digit = split_digit(rem);
+            digits.push(digit);
+            number = (number - digit) / 5;
+        }
+
+        Snafu::new(digits)
+    }
+}
+
+impl From<&Snafu> for BigInt {
+    fn from(snafu: &Snafu) -> BigInt {
+        snafu.digits
+            .iter()
+            .rev()
+            .fold(BigInt::from(0), |number, digit| number * 5 + *digit)
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Snafu, ParseError> {
+        Snafu::from_line(&Line { number: 1, text })
+    }
+}
+
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    /// Adds digit by digit, carrying whenever a sum falls outside -2 to 2.
+    fn add(self, other: &Snafu) -> Snafu {
+        let len = max(self.digits.len(), other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+
+        for idx in 0..len {
+            let a = self.digits.get(idx).copied().unwrap_or(0);
+            let b = other.digits.get(idx).copied().unwrap_or(0);
+
+            let sum = a + b + carry;
+            let digit = split_digit(sum as i64);
+
+            digits.push(digit);
+            carry = (sum - digit) / 5;
+        }
+
+        digits.push(carry);
+
+        Snafu::new(digits)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Snafu {
+        iter.fold(Snafu::default(), |sum, number| &sum + number)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+
+        for digit in self.digits.iter().rev() {
+            let c = match digit {
+                2 => '2',
+                1 => '1',
+                0 => '0',
+                -1 => '-',
+                _ => '=',
+            };
+
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn process(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    error::lines(input)
+        .map(|line| Snafu::from_line(&line))
+        .collect()
+}
+
+pub fn solve_part_one(numbers: &[Snafu]) -> String {
+    numbers
+        .iter()
+        .sum::<Snafu>()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    const CONVERSIONS: [(i64, &str); 10] = [
+        (0, "0"),
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+        (-7, "-="),
+    ];
+
+    #[test]
+    fn conversions() {
+        for (number, snafu) in CONVERSIONS {
+            assert_eq!(Snafu::from(number).to_string(), snafu);
+            assert_eq!(snafu.parse::<Snafu>().unwrap().to_i64(), Some(number));
+        }
+    }
+
+    #[test]
+    fn big_numbers() {
+        let number = BigInt::from(i64::MAX) * BigInt::from(i64::MAX) - 12345;
+        let snafu = Snafu::from(&number);
+
+        assert_eq!(snafu.to_i64(), None);
+        assert_eq!(BigInt::from(&snafu), number);
+        assert_eq!(BigInt::from(&(&snafu + &snafu)), number * 2);
+    }
+
+    #[test]
+    fn extremes() {
+        for number in [i64::MIN, i64::MIN + 1, i64::MAX] {
+            let snafu = Snafu::from(number);
+
+            assert_eq!(BigInt::from(&snafu), BigInt::from(number));
+            assert_eq!(snafu.to_i64(), Some(number));
+        }
+    }
+
+    #[test]
+    fn addition() {
+        for a in -60..60 {
+            for b in -60..60 {
+                assert_eq!((&Snafu::from(a) + &Snafu::from(b)).to_i64(), Some(a + b));
+            }
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(&Day25::parse(EXAMPLE).unwrap()), "2=-1=0");
+    }
+
+    #[test]
+    fn bad_digit() {
+        let err = Day25::parse("1=-0-2\n12311").unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (25, 2, 3));
+    }
+}
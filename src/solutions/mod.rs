@@ -0,0 +1,79 @@
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+
+use crate::{bench::{self, Bench}, error::ParseError, solution::{self, Part, Run, Solution}};
+
+pub struct Day {
+    pub number: u8,
+    pub run: fn(&str, &Part) -> Result<Run, ParseError>,
+    pub bench: fn(&str, &Part, usize, usize) -> Result<Bench, ParseError>,
+}
+
+macro_rules! day {
+    ($solution:ty) => {
+        Day {
+            number: <$solution>::DAY,
+            run: solution::run::<$solution>,
+            bench: bench::bench::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(day_01::Day01),
+    day!(day_02::Day02),
+    day!(day_03::Day03),
+    day!(day_04::Day04),
+    day!(day_05::Day05),
+    day!(day_06::Day06),
+    day!(day_07::Day07),
+    day!(day_08::Day08),
+    day!(day_09::Day09),
+    day!(day_10::Day10),
+    day!(day_11::Day11),
+    day!(day_12::Day12),
+    day!(day_13::Day13),
+    day!(day_14::Day14),
+    day!(day_15::Day15),
+    day!(day_16::Day16),
+    day!(day_17::Day17),
+    day!(day_18::Day18),
+    day!(day_19::Day19),
+    day!(day_20::Day20),
+    day!(day_21::Day21),
+    day!(day_22::Day22),
+    day!(day_23::Day23),
+    day!(day_24::Day24),
+    day!(day_25::Day25),
+];
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub fn latest() -> &'static Day {
+    DAYS.last().unwrap()
+}